@@ -0,0 +1,88 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! With the `embed-inputs` feature, generates a table of all input and example files so that
+//! `aoc::read_file` can serve them from the binary. See `src/lib.rs` for how it is used.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `src/inputs`, `src/examples` and their counterparts in the `src/yYYYY` directories of other years.
+fn data_dirs(src: &Path) -> Vec<PathBuf> {
+    let mut year_dirs = vec![src.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(src) {
+        let mut years: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                let name = p.file_name().unwrap_or_default().to_string_lossy();
+                p.is_dir() && name.starts_with('y') && name[1..].parse::<u16>().is_ok()
+            })
+            .collect();
+        years.sort();
+        year_dirs.extend(years);
+    }
+    year_dirs
+        .iter()
+        .flat_map(|dir| [dir.join("inputs"), dir.join("examples")])
+        .collect()
+}
+
+fn main() {
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        println!("cargo:rerun-if-changed=build.rs");
+        return;
+    }
+
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let src = root.join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut table = String::from("pub static FILES: &[(&str, &str)] = &[\n");
+    // Encrypted inputs stay encrypted in the binary and are decrypted at runtime, see `crypt.rs`.
+    let mut encrypted = String::from("pub static ENCRYPTED: &[(&str, &[u8])] = &[\n");
+    for dir in data_dirs(&src) {
+        println!("cargo:rerun-if-changed={}", dir.display());
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext == "txt" || ext == "enc")
+            })
+            .collect();
+        files.sort();
+
+        for file in files {
+            println!("cargo:rerun-if-changed={}", file.display());
+            let relative = file
+                .strip_prefix(&root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/");
+            if let Some(plain) = relative.strip_suffix(".enc") {
+                encrypted.push_str(&format!(
+                    "    ({:?}, include_bytes!({:?})),\n",
+                    plain,
+                    file.to_string_lossy()
+                ));
+            } else {
+                table.push_str(&format!(
+                    "    ({:?}, include_str!({:?})),\n",
+                    relative,
+                    file.to_string_lossy()
+                ));
+            }
+        }
+    }
+    table.push_str("];\n");
+    encrypted.push_str("];\n");
+    table.push_str(&encrypted);
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded.rs");
+    fs::write(out, table).unwrap();
+}
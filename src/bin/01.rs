@@ -1,38 +1,206 @@
-fn list_of_sums(s: &str) -> impl Iterator<Item = u32> + '_ {
-    s.split("\n\n")
-        .map(|ns| ns.lines().filter_map(|n| n.parse::<u32>().ok()))
-        .map(Iterator::sum::<u32>)
+use anyhow::{anyhow, Context, Result};
+use aoc::Solution;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
+use std::iter::Enumerate;
+
+/// An elf's total calories and its position in the inventory, starting at 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: u32,
+}
+
+/// More calories rank higher, ties go to the elf that comes first.
+impl Ord for Elf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.calories
+            .cmp(&other.calories)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Elf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Sums up the calories of each elf, one line at a time.
+/// A malformed line makes its elf an `Err` with the line number, the elves after it are still read.
+pub struct Elves<L> {
+    lines: Enumerate<L>,
+    index: usize,
+}
+
+impl<L, S> Iterator for Elves<L>
+where
+    L: Iterator<Item = io::Result<S>>,
+    S: AsRef<str>,
+{
+    type Item = Result<Elf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut calories: Option<Result<u32>> = None;
+        for (n, line) in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let line = line.as_ref().trim();
+            if line.is_empty() {
+                if calories.is_some() {
+                    break;
+                }
+                continue;
+            }
+
+            let sum = calories.get_or_insert(Ok(0));
+            if let Ok(total) = sum {
+                *sum = line
+                    .parse::<u32>()
+                    .with_context(|| format!("line {}: invalid calories \"{}\"", n + 1, line))
+                    .and_then(|c| {
+                        total.checked_add(c).ok_or_else(|| {
+                            anyhow!("line {}: calories of elf {} overflow", n + 1, self.index)
+                        })
+                    });
+            }
+        }
+
+        let calories = calories?;
+        let index = self.index;
+        self.index += 1;
+        Some(calories.map(|calories| Elf { index, calories }))
+    }
+}
+
+pub fn elves(input: &str) -> Elves<impl Iterator<Item = io::Result<&str>>> {
+    Elves {
+        lines: input.lines().map(Ok).enumerate(),
+        index: 0,
+    }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    list_of_sums(input).max()
+/// Reads elves from e.g. a file without loading the whole inventory into memory.
+pub fn read_elves<R: BufRead>(reader: R) -> Elves<io::Lines<R>> {
+    Elves {
+        lines: reader.lines().enumerate(),
+        index: 0,
+    }
+}
+
+/// The `k` elves carrying the most calories, most calories first.
+/// Keeps at most `k` elves in a heap, so this runs in O(n log k) and needs O(k) memory.
+pub fn top_k(elves: impl IntoIterator<Item = Elf>, k: usize) -> Vec<Elf> {
+    try_top_k(elves.into_iter().map(Ok), k).unwrap()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut sums: Vec<u32> = list_of_sums(input).collect();
-    sums.sort_unstable();
-    Some(sums.into_iter().rev().take(3).sum())
+/// Like `top_k`, but stops at the first error. Together with `read_elves`, this streams the inventory.
+pub fn try_top_k(elves: impl IntoIterator<Item = Result<Elf>>, k: usize) -> Result<Vec<Elf>> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for elf in elves {
+        heap.push(Reverse(elf?));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(elf)| elf)
+        .collect())
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Parsed = Vec<Elf>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        elves(input).collect()
+    }
+
+    fn part_one(elves: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        let top = top_k(elves.iter().copied(), 1);
+        let elf = top.first().context("the inventory has no elves")?;
+        Ok(Some(elf.calories))
+    }
+
+    fn part_two(elves: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        let top = top_k(elves.iter().copied(), 3);
+        Ok(Some(top.iter().map(|elf| elf.calories).sum()))
+    }
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 1);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    Day01::run();
+}
+
+aoc::tests! {
+    solution = Day01,
+    example: 24000, 45000;
 }
 
 #[cfg(test)]
-mod tests {
+mod elf_tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 1);
-        assert_eq!(part_one(&input), Some(24000));
+    fn test_top_k() {
+        let input = aoc::read_input("examples", 1);
+        let elves: Vec<Elf> = elves(&input).collect::<Result<_>>().unwrap();
+        assert_eq!(
+            top_k(elves.iter().copied(), 3),
+            [
+                Elf {
+                    index: 3,
+                    calories: 24000
+                },
+                Elf {
+                    index: 2,
+                    calories: 11000
+                },
+                Elf {
+                    index: 4,
+                    calories: 10000
+                },
+            ]
+        );
+        assert_eq!(
+            try_top_k(read_elves(input.as_bytes()), 3).unwrap(),
+            top_k(elves.iter().copied(), 3)
+        );
+        assert_eq!(top_k(elves, 0), []);
+    }
+
+    #[test]
+    fn test_malformed() {
+        let results: Vec<_> = elves("1\n2\n\n3\nfour\n5\n\n6").collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[1].as_ref().unwrap_err().to_string(),
+            "line 5: invalid calories \"four\""
+        );
+        assert_eq!(
+            results[2].as_ref().unwrap(),
+            &Elf {
+                index: 2,
+                calories: 6
+            }
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 1);
-        assert_eq!(part_two(&input), Some(45000));
+    fn test_empty() {
+        let elves = Day01::parse("\n\n").unwrap();
+        assert_eq!(
+            Day01::part_one(&elves).unwrap_err().to_string(),
+            "the inventory has no elves"
+        );
     }
 }
@@ -1,130 +1,346 @@
+use anyhow::{bail, Context, Result};
+use aoc::Solution;
+use itertools::Itertools;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+/// Points awarded per round: the points for the outcome plus `first_throw + i` for throwing `i`.
 #[derive(Clone, Copy, Debug)]
-enum Game {
-    Win = 6,
-    Lose = 3,
-    Tie = 0,
+pub struct Scoring {
+    pub lose: u32,
+    pub draw: u32,
+    pub win: u32,
+    pub first_throw: u32,
+}
+
+impl Scoring {
+    pub const PUZZLE: Scoring = Scoring {
+        lose: 0,
+        draw: 3,
+        win: 6,
+        first_throw: 1,
+    };
+}
+
+/// A cyclic game with `throws` throws, numbered from 0. Every throw beats the
+/// `throws / 2` throws before it (mod `throws`) and loses to the ones after it.
+#[derive(Clone, Copy, Debug)]
+pub struct Game {
+    throws: u8,
+    scoring: Scoring,
 }
 
 impl Game {
-    fn from(opponent: Throw, me: Throw) -> Game {
-        if opponent.wins_against() == me {
-            Game::Lose
-        } else if opponent.loses_to() == me {
-            Game::Win
-        } else {
-            Game::Tie
-        }
+    /// Rock, Paper, Scissors.
+    pub const ROCK_PAPER_SCISSORS: Game = Game::new(3, Scoring::PUZZLE);
+    /// Rock, Spock, Paper, Lizard, Scissors.
+    pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: Game = Game::new(5, Scoring::PUZZLE);
+
+    /// Panics unless `throws` is odd, otherwise some throws would tie against others.
+    pub const fn new(throws: u8, scoring: Scoring) -> Self {
+        assert!(
+            throws % 2 == 1,
+            "a cyclic game needs an odd number of throws"
+        );
+        Game { throws, scoring }
     }
-    fn value(self) -> u32 {
-        self as u32
+
+    pub fn throws(&self) -> u8 {
+        self.throws
     }
 
-    fn needed_throw(self, opponent: Throw) -> Throw {
-        match self {
-            Game::Win => opponent.loses_to(),
-            Game::Lose => opponent.wins_against(),
-            Game::Tie => opponent,
+    fn check_throw(&self, throw: u8) {
+        assert!(
+            throw < self.throws,
+            "throw {} is out of range for a game of {} throws",
+            throw,
+            self.throws
+        );
+    }
+
+    /// Panics if a throw is out of range.
+    pub fn outcome(&self, opponent: u8, me: u8) -> Outcome {
+        self.check_throw(opponent);
+        self.check_throw(me);
+        let throws = u16::from(self.throws);
+        match (u16::from(me) + throws - u16::from(opponent)) % throws {
+            0 => Outcome::Draw,
+            d if d <= throws / 2 => Outcome::Win,
+            _ => Outcome::Lose,
         }
     }
+
+    /// A throw that leads to `outcome` against `opponent`.
+    pub fn needed_throw(&self, opponent: u8, outcome: Outcome) -> u8 {
+        self.check_throw(opponent);
+        let throws = u16::from(self.throws);
+        let steps = match outcome {
+            Outcome::Draw => 0,
+            Outcome::Win => 1,
+            Outcome::Lose => throws - 1,
+        };
+        ((u16::from(opponent) + steps) % throws) as u8
+    }
+
+    pub fn score(&self, me: u8, outcome: Outcome) -> u32 {
+        self.check_throw(me);
+        let points = match outcome {
+            Outcome::Lose => self.scoring.lose,
+            Outcome::Draw => self.scoring.draw,
+            Outcome::Win => self.scoring.win,
+        };
+        points + self.scoring.first_throw + u32::from(me)
+    }
+
+    pub fn play(&self, opponent: u8, me: u8) -> u32 {
+        self.score(me, self.outcome(opponent, me))
+    }
+}
+
+/// Index of a letter among the `count` letters starting at `first`, e.g. `B` is 1 relative to `A`.
+fn letter(s: &str, first: u8, count: u8) -> Result<u8> {
+    match s.as_bytes() {
+        [c] if (first..first + count).contains(c) => Ok(c - first),
+        _ => bail!(
+            "invalid letter \"{}\", expected {}-{}",
+            s,
+            first as char,
+            (first + count - 1) as char
+        ),
+    }
+}
+
+fn parse_round(line: &str) -> Result<(u8, u8)> {
+    let (left, right) = line.split_once(' ').unwrap_or((line, ""));
+    Ok((letter(left, b'A', 3)?, letter(right, b'X', 3)?))
+}
+
+/// The strategy guide as pairs of letter indices, i.e. `A`-`C` and `X`-`Z` as `0`-`2`.
+pub fn parse_guide(input: &str) -> Result<Vec<(u8, u8)>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, line)| parse_round(line).with_context(|| format!("line {}", n + 1)))
+        .collect()
+}
+
+const THROWS: [&str; 3] = ["Rock", "Paper", "Scissors"];
+const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+/// How the second column of the strategy guide is read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Throw,
+    Outcome,
 }
+
+/// Maps each of `X`, `Y`, `Z` to a distinct throw or outcome of rock-paper-scissors.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Throw {
-    Rock = 0,
-    Paper = 1,
-    Scissors = 2,
+pub struct Mapping {
+    pub column: Column,
+    pub letters: [u8; 3],
 }
 
-impl Throw {
-    fn value(self) -> u32 {
-        self as u32 + 1
+impl Mapping {
+    /// The reading of part one, `X`, `Y`, `Z` are the throws in order.
+    pub const THROWS: Mapping = Mapping {
+        column: Column::Throw,
+        letters: [0, 1, 2],
+    };
+    /// The reading of part two, `X`, `Y`, `Z` are lose, draw and win.
+    pub const OUTCOMES: Mapping = Mapping {
+        column: Column::Outcome,
+        letters: [0, 1, 2],
+    };
+
+    /// All 12 mappings, the 6 bijections onto throws followed by the 6 onto outcomes.
+    pub fn all() -> impl Iterator<Item = Mapping> {
+        [Column::Throw, Column::Outcome]
+            .into_iter()
+            .cartesian_product((0..3).permutations(3))
+            .map(|(column, letters)| Mapping {
+                column,
+                letters: [letters[0], letters[1], letters[2]],
+            })
     }
 
-    fn wins_against(self) -> Self {
-        match self {
-            Throw::Rock => Throw::Scissors,
-            Throw::Paper => Throw::Rock,
-            Throw::Scissors => Throw::Paper,
+    pub fn round(&self, opponent: u8, letter: u8) -> u32 {
+        let game = Game::ROCK_PAPER_SCISSORS;
+        let value = self.letters[letter as usize];
+        match self.column {
+            Column::Throw => game.play(opponent, value),
+            Column::Outcome => {
+                let outcome = OUTCOMES[value as usize];
+                game.score(game.needed_throw(opponent, outcome), outcome)
+            }
         }
     }
 
-    fn loses_to(self) -> Self {
-        match self {
-            Throw::Rock => Throw::Paper,
-            Throw::Paper => Throw::Scissors,
-            Throw::Scissors => Throw::Rock,
-        }
+    pub fn score(&self, guide: &[(u8, u8)]) -> u32 {
+        guide
+            .iter()
+            .map(|&(opponent, letter)| self.round(opponent, letter))
+            .sum()
     }
 }
 
-fn decrypt_p1(s: &str) -> Throw {
-    match s {
-        "A" | "X" => Throw::Rock,
-        "B" | "Y" => Throw::Paper,
-        "C" | "Z" => Throw::Scissors,
-        _ => unreachable!(),
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &value) in self.letters.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            let letter = (b'X' + i as u8) as char;
+            match self.column {
+                Column::Throw => write!(f, "{}={}", letter, THROWS[value as usize])?,
+                Column::Outcome => write!(f, "{}={:?}", letter, OUTCOMES[value as usize])?,
+            }
+        }
+        Ok(())
     }
 }
 
-fn decrypt_p2((opponent, game): (&str, &str)) -> (Throw, Game) {
-    (
-        match opponent {
-            "A" => Throw::Rock,
-            "B" => Throw::Paper,
-            "C" => Throw::Scissors,
-            _ => unreachable!(),
-        },
-        match game {
-            "X" => Game::Lose,
-            "Y" => Game::Tie,
-            "Z" => Game::Win,
-            _ => unreachable!(),
-        },
-    )
+/// Mappings under which following the guide scores exactly `target`.
+pub fn mappings_with_score(guide: &[(u8, u8)], target: u32) -> Vec<Mapping> {
+    Mapping::all()
+        .filter(|m| m.score(guide) == target)
+        .collect()
 }
 
-fn split_input(input: &str) -> impl Iterator<Item = (&str, &str)> {
-    input
-        .lines()
-        .map(|s| s.split_once(' ').expect("Only A-C and X-Z"))
+/// Mappings that agree with every known `(round, score)`, rounds are numbered from 0.
+pub fn mappings_with_rounds(guide: &[(u8, u8)], known: &[(usize, u32)]) -> Vec<Mapping> {
+    Mapping::all()
+        .filter(|m| {
+            known.iter().all(|&(round, score)| {
+                guide
+                    .get(round)
+                    .is_some_and(|&(opponent, letter)| m.round(opponent, letter) == score)
+            })
+        })
+        .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        split_input(input)
-            .map(|(left, right)| (decrypt_p1(left), decrypt_p1(right)))
-            .map(|(opponent, me)| me.value() + Game::from(opponent, me).value())
-            .sum(),
-    )
+/// The highest score any mapping achieves, along with all mappings that achieve it.
+pub fn best_mappings(guide: &[(u8, u8)]) -> (u32, Vec<Mapping>) {
+    let scored: Vec<(u32, Mapping)> = Mapping::all().map(|m| (m.score(guide), m)).collect();
+    let best = scored.iter().map(|(score, _)| *score).max().unwrap_or(0);
+    let mappings = scored
+        .into_iter()
+        .filter(|(score, _)| *score == best)
+        .map(|(_, m)| m)
+        .collect();
+    (best, mappings)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        split_input(input)
-            .map(decrypt_p2)
-            .map(|(opponent, game)| game.needed_throw(opponent).value() + game.value())
-            .sum(),
-    )
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Parsed = Vec<(u8, u8)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_guide(input)
+    }
+
+    fn part_one(guide: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(Some(Mapping::THROWS.score(guide)))
+    }
+
+    fn part_two(guide: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(Some(Mapping::OUTCOMES.score(guide)))
+    }
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 2);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    Day02::run();
+}
+
+aoc::tests! {
+    solution = Day02,
+    example: 15, 12;
 }
 
 #[cfg(test)]
-mod tests {
+mod game_tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 2);
-        assert_eq!(part_one(&input), Some(15));
+    fn test_rock_paper_scissors_lizard_spock() {
+        let game = Game::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+        let [rock, spock, paper, lizard, scissors] = [0, 1, 2, 3, 4];
+        assert_eq!(game.outcome(rock, paper), Outcome::Win);
+        assert_eq!(game.outcome(rock, spock), Outcome::Win);
+        assert_eq!(game.outcome(rock, lizard), Outcome::Lose);
+        assert_eq!(game.outcome(rock, scissors), Outcome::Lose);
+        assert_eq!(game.outcome(lizard, lizard), Outcome::Draw);
+
+        for opponent in 0..game.throws() {
+            let wins = (0..game.throws())
+                .filter(|&me| game.outcome(opponent, me) == Outcome::Win)
+                .count();
+            assert_eq!(wins, 2);
+            for outcome in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+                let me = game.needed_throw(opponent, outcome);
+                assert_eq!(game.outcome(opponent, me), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_game() {
+        let game = Game::new(201, Scoring::PUZZLE);
+        assert_eq!(game.outcome(0, 200), Outcome::Lose);
+        assert_eq!(game.outcome(200, 0), Outcome::Win);
+        assert_eq!(game.needed_throw(200, Outcome::Win), 0);
+        assert_eq!(game.needed_throw(0, Outcome::Lose), 200);
     }
 
     #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 2);
-        assert_eq!(part_two(&input), Some(12));
+    #[should_panic(expected = "throw 5 is out of range")]
+    fn test_throw_out_of_range() {
+        Game::ROCK_PAPER_SCISSORS.outcome(5, 0);
+    }
+
+    #[test]
+    fn test_parse_guide() {
+        assert_eq!(parse_guide("A Y\nC Z").unwrap(), [(0, 1), (2, 2)]);
+        let error = |input| format!("{:#}", parse_guide(input).unwrap_err());
+        assert_eq!(
+            error("A X\nD X"),
+            "line 2: invalid letter \"D\", expected A-C"
+        );
+        assert_eq!(error("A a"), "line 1: invalid letter \"a\", expected X-Z");
+        assert_eq!(error("AX"), "line 1: invalid letter \"AX\", expected A-C");
+    }
+
+    #[test]
+    fn test_decryption() {
+        let guide = Day02::parse(&Day02::example()).unwrap();
+        assert_eq!(Mapping::all().count(), 12);
+        assert!(mappings_with_score(&guide, 15).contains(&Mapping::THROWS));
+        assert!(mappings_with_score(&guide, 12).contains(&Mapping::OUTCOMES));
+        assert_eq!(
+            mappings_with_rounds(&guide, &[(0, 8), (1, 1), (2, 6)]),
+            [
+                Mapping::THROWS,
+                Mapping {
+                    column: Column::Outcome,
+                    letters: [0, 2, 1]
+                }
+            ]
+        );
+
+        let (best, mappings) = best_mappings(&guide);
+        assert!(mappings.iter().all(|m| m.score(&guide) == best));
+        assert!(Mapping::all().all(|m| m.score(&guide) <= best));
+        assert_eq!(Mapping::OUTCOMES.to_string(), "X=Lose Y=Draw Z=Win");
     }
 }
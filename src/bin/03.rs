@@ -1,67 +1,215 @@
-use itertools::Itertools;
+use anyhow::{Error, Result};
+use aoc::Solution;
+use bit_set::BitSet;
 
-type Rucksack<'a> = (&'a str, &'a str);
-type Group<'a> = (&'a str, &'a str, &'a str);
+/// A priority for each item, items without one are unknown. Only ASCII items are supported.
+pub struct Priorities {
+    priorities: [Option<u32>; 128],
+}
+
+impl Priorities {
+    pub fn new(table: impl IntoIterator<Item = (char, u32)>) -> Result<Self, String> {
+        let mut priorities = [None; 128];
+        for (item, priority) in table {
+            if !item.is_ascii() {
+                return Err(format!("item '{}' is not ASCII", item));
+            }
+            if priorities[item as usize].replace(priority).is_some() {
+                return Err(format!("duplicate item '{}'", item));
+            }
+        }
+        Ok(Priorities { priorities })
+    }
+
+    /// The n-th item of `alphabet` has priority n.
+    pub fn from_alphabet(alphabet: &str) -> Result<Self, String> {
+        Self::new(alphabet.chars().zip(1..))
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.priorities.get(item as usize).copied().flatten()
+    }
 
-fn rucksacks(input: &str) -> impl Iterator<Item = Rucksack> {
-    input.lines().map(|line| line.split_at(line.len() / 2))
+    /// The set of all items in `items`.
+    pub fn items(&self, items: &str) -> Result<ItemSet, String> {
+        items
+            .chars()
+            .map(|item| match self.priority(item) {
+                Some(_) => Ok(item as usize),
+                None => Err(format!("unknown item '{}'", item)),
+            })
+            .collect::<Result<BitSet, _>>()
+            .map(ItemSet)
+    }
 }
 
-fn groups(input: &str) -> impl Iterator<Item = Group> {
-    input.split_ascii_whitespace().tuples()
+/// Priorities of the puzzle, `a`-`z` are 1-26 and `A`-`Z` are 27-52.
+impl Default for Priorities {
+    fn default() -> Self {
+        Priorities::from_alphabet("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap()
+    }
 }
 
-fn bits(line: &str) -> u64 {
-    line.bytes()
-        .map(priority)
-        .fold(0, |bits, bit| bits | 1 << bit)
+/// A set of ASCII items, stored by their codes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemSet(BitSet);
+
+impl ItemSet {
+    pub fn intersect_with(&mut self, other: &ItemSet) {
+        self.0.intersect_with(&other.0);
+    }
+
+    /// Items in ASCII order.
+    pub fn items(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.iter().map(|item| item as u8 as char)
+    }
+
+    /// The sum of the priorities of all items, items unknown to `priorities` count as 0.
+    pub fn priority(&self, priorities: &Priorities) -> u32 {
+        self.items()
+            .filter_map(|item| priorities.priority(item))
+            .sum()
+    }
 }
 
-fn find_extra_item((left, right): Rucksack) -> u32 {
-    let common = bits(left) & bits(right);
-    u64::BITS - common.leading_zeros()
+/// The items all of `rucksacks` have in common.
+pub fn common_items<'a>(
+    priorities: &Priorities,
+    rucksacks: impl IntoIterator<Item = &'a str>,
+) -> Result<ItemSet, String> {
+    rucksacks
+        .into_iter()
+        .map(|rucksack| priorities.items(rucksack))
+        .reduce(|common, items| {
+            let mut common = common?;
+            common.intersect_with(&items?);
+            Ok(common)
+        })
+        .unwrap_or_else(|| Ok(ItemSet(BitSet::new())))
 }
 
-fn find_badge((a, b, c): Group) -> u32 {
-    let common = bits(a) & bits(b) & bits(c);
-    u64::BITS - common.leading_zeros()
+/// Items that are in both compartments of each rucksack.
+pub fn misplaced_items(
+    priorities: &Priorities,
+    rucksacks: &[String],
+) -> Result<Vec<ItemSet>, String> {
+    rucksacks
+        .iter()
+        .map(|line| {
+            if let Some(item) = line.chars().find(|item| !item.is_ascii()) {
+                return Err(format!("unknown item '{}'", item));
+            }
+            if !line.len().is_multiple_of(2) {
+                return Err(format!("rucksack \"{}\" has an odd number of items", line));
+            }
+            let (left, right) = line.split_at(line.len() / 2);
+            common_items(priorities, [left, right])
+        })
+        .collect()
 }
 
-fn priority(c: u8) -> u8 {
-    match c {
-        b'a'..=b'z' => c - b'a',
-        b'A'..=b'Z' => c - b'A' + 26,
-        _ => unreachable!(),
+/// Items that all rucksacks of each group of `size` elves have in common.
+pub fn badges(
+    priorities: &Priorities,
+    rucksacks: &[String],
+    size: usize,
+) -> Result<Vec<ItemSet>, String> {
+    if size == 0 || !rucksacks.len().is_multiple_of(size) {
+        return Err(format!(
+            "{} rucksacks can't be split into groups of {}",
+            rucksacks.len(),
+            size
+        ));
     }
+    rucksacks
+        .chunks(size)
+        .map(|group| common_items(priorities, group.iter().map(String::as_str)))
+        .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(rucksacks(input).map(find_extra_item).sum())
+fn total_priority(priorities: &Priorities, items: Vec<ItemSet>) -> u32 {
+    items.iter().map(|items| items.priority(priorities)).sum()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    Some(groups(input).map(find_badge).sum())
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Parsed = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_one(rucksacks: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        let priorities = Priorities::default();
+        let items = misplaced_items(&priorities, rucksacks).map_err(Error::msg)?;
+        Ok(Some(total_priority(&priorities, items)))
+    }
+
+    fn part_two(rucksacks: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        let priorities = Priorities::default();
+        let badges = badges(&priorities, rucksacks, 3).map_err(Error::msg)?;
+        Ok(Some(total_priority(&priorities, badges)))
+    }
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 3);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    Day03::run();
+}
+
+aoc::tests! {
+    solution = Day03,
+    example: 157, 70;
 }
 
 #[cfg(test)]
-mod tests {
+mod item_tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 3);
-        assert_eq!(part_one(&input), Some(157));
+    fn test_common_items() {
+        let priorities = Priorities::from_alphabet("abc123").unwrap();
+        let common = common_items(&priorities, ["ab1c3", "3ba", "a23b"]).unwrap();
+        assert_eq!(common.items().collect::<String>(), "3ab");
+        assert_eq!(common.priority(&priorities), 1 + 2 + 6);
+
+        assert_eq!(
+            common_items(&priorities, ["ab", "aZ"]),
+            Err("unknown item 'Z'".into())
+        );
+        assert!(Priorities::from_alphabet("abca").is_err());
+        assert!(Priorities::from_alphabet("aé").is_err());
+    }
+
+    #[test]
+    fn test_priority_table() {
+        let priorities = Priorities::new([('a', 10), ('b', 5), ('c', 1)]).unwrap();
+        let items = misplaced_items(&priorities, &["abab".into(), "acbc".into()]).unwrap();
+        assert_eq!(total_priority(&priorities, items), 10 + 5 + 1);
+        assert_eq!(
+            misplaced_items(&priorities, &["éa".into()]),
+            Err("unknown item 'é'".into())
+        );
+        assert_eq!(
+            misplaced_items(&priorities, &["aba".into()]),
+            Err("rucksack \"aba\" has an odd number of items".into())
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 3);
-        assert_eq!(part_two(&input), Some(70));
+    fn test_badges() {
+        let rucksacks = Day03::parse(&Day03::example()).unwrap();
+        let priorities = Priorities::default();
+        let badges: Vec<String> = badges(&priorities, &rucksacks, 2)
+            .unwrap()
+            .iter()
+            .map(|badge| badge.items().collect())
+            .collect();
+        assert_eq!(badges.len(), 3);
+        assert!(badges.iter().all(|badge| !badge.is_empty()));
+        assert!(super::badges(&priorities, &rucksacks, 4).is_err());
     }
 }
@@ -1,63 +1,106 @@
-use itertools::Itertools;
+use anyhow::{Context, Error, Result};
+use aoc::helpers::{Interval, IntervalSet};
+use aoc::Solution;
+use std::process;
 
-fn is_fully_contained(elf: &&str) -> bool {
-    let (ll, lr, rl, rr) = elf
-        .replace('-', ",")
-        .split(',')
-        .map(|x| x.parse::<u8>().unwrap())
-        .tuples()
-        .next()
-        .expect("There must be exactly four elements");
+type Pair = (Interval, Interval);
 
-    (rr >= lr && rl <= ll) || ll <= rl && lr >= rr
+fn parse_pair(line: &str) -> Result<Pair> {
+    let (left, right) = line
+        .split_once(',')
+        .with_context(|| format!("pairs are separated by a comma: \"{}\"", line))?;
+    Ok((
+        left.parse().map_err(Error::msg)?,
+        right.parse().map_err(Error::msg)?,
+    ))
 }
 
-fn is_overlapping(elf: &&str) -> bool {
-    let (ll, lr, rl, rr) = elf
-        .replace('-', ",")
-        .split(',')
-        .map(|x| x.parse::<u8>().unwrap())
-        .tuples()
-        .next()
-        .expect("There must be exactly four elements");
+fn is_fully_contained((left, right): &&Pair) -> bool {
+    left.contains(right) || right.contains(left)
+}
 
-    (ll >= rl && ll <= rr)
-        || (lr >= rl && lr <= rr)
-        || (rl >= ll && rl <= lr)
-        || (rr >= ll && rr <= lr)
+fn is_overlapping((left, right): &&Pair) -> bool {
+    left.overlaps(right)
 }
 
-fn solve_part(input: &str, filter_fn: impl Fn(&&str) -> bool) -> Option<usize> {
-    Some(input.lines().filter(filter_fn).count())
+/// The number of sections assigned to both elves of each pair.
+pub fn overlap_sizes(pairs: &[Pair]) -> Vec<u64> {
+    pairs
+        .iter()
+        .map(|(left, right)| left.intersection(right).map_or(0, |i| i.len()))
+        .collect()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    solve_part(input, is_fully_contained)
+/// The number of sections assigned to at least one elf.
+pub fn sections_covered(pairs: &[Pair]) -> u64 {
+    pairs
+        .iter()
+        .flat_map(|(left, right)| [left.clone(), right.clone()])
+        .collect::<IntervalSet>()
+        .len()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    solve_part(input, is_overlapping)
+fn solve_part(pairs: &[Pair], filter_fn: impl Fn(&&Pair) -> bool) -> Option<usize> {
+    Some(pairs.iter().filter(filter_fn).count())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Parsed = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        input.lines().map(parse_pair).collect()
+    }
+
+    fn part_one(pairs: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(solve_part(pairs, is_fully_contained))
+    }
+
+    fn part_two(pairs: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(solve_part(pairs, is_overlapping))
+    }
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 4);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    // Parsed once and shared with the section stats, so the timings below exclude parsing.
+    let pairs = Day04::parse(&Day04::input()).unwrap_or_else(|e| {
+        eprintln!("{:#}", e);
+        process::exit(1);
+    });
+    aoc::print_part(1, || Day04::part_one(&pairs));
+    aoc::print_part(2, || Day04::part_two(&pairs));
+
+    if aoc::part_enabled(1) && aoc::part_enabled(2) {
+        let overlaps = overlap_sizes(&pairs);
+        println!(
+            "Overlapping sections: {} in total, at most {} in one pair",
+            overlaps.iter().sum::<u64>(),
+            overlaps.iter().max().unwrap_or(&0)
+        );
+        println!("Sections covered: {}", sections_covered(&pairs));
+    }
+}
+
+aoc::tests! {
+    solution = Day04,
+    example: 2, 4;
+    cases: [("1000-5000,4000-9000", 0, 1)];
 }
 
 #[cfg(test)]
-mod tests {
+mod section_tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 4);
-        assert_eq!(part_one(&input), Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 4);
-        assert_eq!(part_two(&input), Some(4));
+    fn test_sections() {
+        let pairs = Day04::parse(&Day04::example()).unwrap();
+        assert_eq!(overlap_sizes(&pairs), [0, 0, 1, 5, 1, 3]);
+        assert_eq!(sections_covered(&pairs), 8);
+        let pairs = Day04::parse("1000-5000,4000-9000").unwrap();
+        assert_eq!(sections_covered(&pairs), 8001);
     }
 }
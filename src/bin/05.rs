@@ -1,18 +1,17 @@
+use anyhow::{Context, Result};
+use aoc::Solution;
+
 type Stack = Vec<u8>;
 
 #[derive(Clone, Copy, Debug)]
-struct Move {
+pub struct Move {
     num: usize,
     src: usize,
     dest: usize,
 }
 
-fn parts(input: &str) -> (&str, &str) {
-    input.split_once("\n\n").unwrap()
-}
-
 fn parse_stack(stack: &str) -> Vec<Stack> {
-    let mut s = vec![Vec::with_capacity(20); 9];
+    let mut s: Vec<Stack> = (0..9).map(|_| Vec::with_capacity(20)).collect();
     for l in stack.lines().rev().skip(1) {
         l.bytes()
             .skip(1)
@@ -24,14 +23,23 @@ fn parse_stack(stack: &str) -> Vec<Stack> {
     s
 }
 
-fn parse_moves(moves: &str) -> impl Iterator<Item = Move> + '_ {
-    moves.lines().map(|line| {
-        let mut parts = line.split_whitespace().skip(1).step_by(2);
-        let num = parts.next().unwrap().parse::<usize>().unwrap();
-        let src = parts.next().unwrap().parse::<usize>().unwrap() - 1;
-        let dest = parts.next().unwrap().parse::<usize>().unwrap() - 1;
-        Move { num, src, dest }
-    })
+fn parse_move(line: &str) -> Option<Move> {
+    let mut parts = line
+        .split_whitespace()
+        .skip(1)
+        .step_by(2)
+        .map(|n| n.parse::<usize>().ok());
+    let num = parts.next()??;
+    let src = parts.next()??.checked_sub(1)?;
+    let dest = parts.next()??.checked_sub(1)?;
+    Some(Move { num, src, dest })
+}
+
+fn parse_moves(moves: &str) -> Result<Vec<Move>> {
+    moves
+        .lines()
+        .map(|line| parse_move(line).with_context(|| format!("invalid move \"{}\"", line)))
+        .collect()
 }
 
 fn run_moves_p1(mut stacks: Vec<Stack>, moves: impl Iterator<Item = Move>) -> Vec<Stack> {
@@ -45,7 +53,7 @@ fn run_moves_p1(mut stacks: Vec<Stack>, moves: impl Iterator<Item = Move>) -> Ve
 }
 
 fn run_moves_p2(mut stacks: Vec<Stack>, moves: impl Iterator<Item = Move>) -> Vec<Stack> {
-    let mut swap = vec![0; 64];
+    let mut swap = [0; 64];
     for Move { num, src, dest } in moves {
         let swap = &mut swap[..num];
         let len = stacks[src].len();
@@ -56,51 +64,46 @@ fn run_moves_p2(mut stacks: Vec<Stack>, moves: impl Iterator<Item = Move>) -> Ve
     }
     stacks
 }
-pub fn part_one(input: &str) -> Option<String> {
-    let (stacks, moves) = parts(input);
-    let (stacks, moves) = (parse_stack(stacks), parse_moves(moves));
-    let stacks = run_moves_p1(stacks, moves);
-    Some(
-        stacks
-            .into_iter()
-            .filter_map(|stack| stack.last().copied())
-            .map(|c| c as char)
-            .collect::<String>(),
-    )
-}
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (stacks, moves) = parts(input);
-    let (stacks, moves) = (parse_stack(stacks), parse_moves(moves));
-    let stacks = run_moves_p2(stacks, moves);
-    Some(
-        stacks
-            .into_iter()
-            .filter_map(|stack| stack.last().copied())
-            .map(|c| c as char)
-            .collect::<String>(),
-    )
+fn top_crates(stacks: Vec<Stack>) -> String {
+    stacks
+        .into_iter()
+        .filter_map(|stack| stack.last().copied())
+        .map(|c| c as char)
+        .collect()
 }
 
-fn main() {
-    let input = &aoc::read_file("inputs", 5);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
-}
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Parsed = (Vec<Stack>, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let (stacks, moves) = input
+            .split_once("\n\n")
+            .context("stacks and moves are separated by a blank line")?;
+        Ok((parse_stack(stacks), parse_moves(moves)?))
+    }
 
-    #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 5);
-        assert_eq!(part_one(&input), Some("CMZ".to_string()));
+    fn part_one((stacks, moves): &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        let stacks = run_moves_p1(stacks.clone(), moves.iter().copied());
+        Ok(Some(top_crates(stacks)))
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 5);
-        assert_eq!(part_two(&input), Some("MCD".to_string()));
+    fn part_two((stacks, moves): &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        let stacks = run_moves_p2(stacks.clone(), moves.iter().copied());
+        Ok(Some(top_crates(stacks)))
     }
 }
+
+fn main() {
+    Day05::run();
+}
+
+aoc::tests! {
+    solution = Day05,
+    example: "CMZ", "MCD";
+}
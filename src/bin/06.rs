@@ -1,3 +1,6 @@
+use anyhow::Result;
+use aoc::Solution;
+
 fn no_duplicates(window: &[u8], size: usize) -> bool {
     let n = window
         .iter()
@@ -14,51 +17,38 @@ fn message_start(input: &str, size: usize) -> Option<usize> {
         .map(|x| x + size)
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    message_start(input, 4)
-}
-
-pub fn part_two(input: &str) -> Option<usize> {
-    message_start(input, 14)
-}
+pub struct Day06;
 
-fn main() {
-    let input = &aoc::read_file("inputs", 6);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
-}
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Parsed = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.to_string())
+    }
 
-    #[test]
-    fn test_part_one() {
-        let inputs = [
-            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
-            "bvwbjplbgvbhsrlpgdmjqwftvncz",
-            "nppdvjthqldpwncqszvftbrmjlhg",
-            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
-            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
-        ];
-        assert_eq!(
-            inputs.map(part_one),
-            [Some(7), Some(5), Some(6), Some(10), Some(11)]
-        );
+    fn part_one(input: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(message_start(input, 4))
     }
 
-    #[test]
-    fn test_part_two() {
-        let inputs = [
-            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
-            "bvwbjplbgvbhsrlpgdmjqwftvncz",
-            "nppdvjthqldpwncqszvftbrmjlhg",
-            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
-            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
-        ];
-        assert_eq!(
-            inputs.map(part_two),
-            [Some(19), Some(23), Some(23), Some(29), Some(26)]
-        );
+    fn part_two(input: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(message_start(input, 14))
     }
 }
+
+fn main() {
+    Day06::run();
+}
+
+aoc::tests! {
+    solution = Day06,
+    cases: [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+}
@@ -1,4 +1,5 @@
-use anyhow::Error;
+use anyhow::{Error, Result};
+use aoc::Solution;
 use hashbrown::hash_map::HashMap;
 use std::str::FromStr;
 
@@ -42,7 +43,7 @@ impl FromStr for Line {
 }
 
 #[derive(Debug, Default)]
-struct DirectoryTree {
+pub struct DirectoryTree {
     subdirs: HashMap<String, DirectoryTree>,
     files: Vec<File>,
     size: u32,
@@ -63,7 +64,7 @@ fn part1_small_dir_total(root: &DirectoryTree) -> u32 {
     subdir_total + if root.size <= 100_000 { root.size } else { 0 }
 }
 
-fn part2_delete_smallest(root: DirectoryTree) -> u32 {
+fn part2_delete_smallest(root: &DirectoryTree) -> u32 {
     let needed_space = 30_000_000 - (70_000_000 - root.size);
     let mut dirs = Vec::from_iter(root.subdirs.values());
     let mut min = 70_000_000;
@@ -113,39 +114,38 @@ fn make_tree(info: impl Iterator<Item = Line>) -> DirectoryTree {
     tree
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let lines = input.lines().map(|line| line.parse::<Line>().unwrap());
-    let mut tree = make_tree(lines);
-    tree.populate_sizes();
-    Some(part1_small_dir_total(&tree))
-}
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Parsed = DirectoryTree;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let lines = input
+            .lines()
+            .map(|line| line.parse::<Line>())
+            .collect::<Result<Vec<_>>>()?;
+        let mut tree = make_tree(lines.into_iter());
+        tree.populate_sizes();
+        Ok(tree)
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let lines = input.lines().map(|line| line.parse::<Line>().unwrap());
-    let mut tree = make_tree(lines);
-    tree.populate_sizes();
-    Some(part2_delete_smallest(tree))
+    fn part_one(tree: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(Some(part1_small_dir_total(tree)))
+    }
+
+    fn part_two(tree: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(Some(part2_delete_smallest(tree)))
+    }
 }
 
 fn main() {
-    let input = &aoc::read_file("inputs", 7);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
+    Day07::run();
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 7);
-        assert_eq!(part_one(&input), Some(95437));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 7);
-        assert_eq!(part_two(&input), Some(24933642));
-    }
+aoc::tests! {
+    solution = Day07,
+    example: 95437, 24933642;
 }
@@ -1,3 +1,5 @@
+use anyhow::Result;
+use aoc::Solution;
 use itertools::Itertools;
 
 type Direction = usize;
@@ -16,7 +18,7 @@ fn next_point(point: usize, direction: Direction, size: usize) -> Option<usize>
         U => point.checked_sub(size),
         D => ((point + size) < square).then_some(point + size),
         L => ((point - 1) % size != size - 1).then_some(point - 1),
-        R => ((point + 1) % size != 0).then_some(point + 1),
+        R => (!(point + 1).is_multiple_of(size)).then_some(point + 1),
         _ => unreachable!(),
     }
 }
@@ -77,41 +79,35 @@ fn max_viewing_distance(bytes: &[u8]) -> u32 {
         .unwrap()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let bytes = input
-        .lines()
-        .flat_map(|line| line.as_bytes().iter().map(|b| b - b'0'))
-        .collect_vec();
-    Some(visible_from_outside(&bytes))
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    let bytes = input
-        .lines()
-        .flat_map(|line| line.as_bytes().iter().map(|b| b - b'0'))
-        .collect_vec();
-    Some(max_viewing_distance(&bytes))
-}
+pub struct Day08;
 
-fn main() {
-    let input = &aoc::read_file("inputs", 8);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
-}
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Parsed = Vec<u8>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input
+            .lines()
+            .flat_map(|line| line.as_bytes().iter().map(|b| b - b'0'))
+            .collect_vec())
+    }
 
-    #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 8);
-        assert_eq!(part_one(&input), Some(21));
+    fn part_one(bytes: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(Some(visible_from_outside(bytes)))
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 8);
-        assert_eq!(part_two(&input), Some(8));
+    fn part_two(bytes: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(Some(max_viewing_distance(bytes)))
     }
 }
+
+fn main() {
+    Day08::run();
+}
+
+aoc::tests! {
+    solution = Day08,
+    example: 21, 8;
+}
@@ -1,3 +1,5 @@
+use anyhow::Result;
+use aoc::Solution;
 use hashbrown::HashSet;
 use std::hash::Hash;
 
@@ -22,7 +24,7 @@ impl From<&str> for Direction {
 }
 
 #[derive(Clone, Copy, Debug)]
-struct Move {
+pub struct Move {
     dir: Direction,
     count: u16,
 }
@@ -74,35 +76,32 @@ where
     tail_positions.len() as u32
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let moves = input.lines().map(|line| line.into());
-    Some(positions_visited::<2>(moves))
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    let moves = input.lines().map(|line| line.into());
-    Some(positions_visited::<10>(moves))
-}
+pub struct Day09;
 
-fn main() {
-    let input = &aoc::read_file("inputs", 9);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
-}
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Parsed = Vec<Move>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.lines().map(Move::from).collect())
+    }
 
-    #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", 9);
-        assert_eq!(part_one(&input), Some(13));
+    fn part_one(moves: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(Some(positions_visited::<2>(moves.iter().copied())))
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", 9);
-        assert_eq!(part_two(&input), Some(1));
+    fn part_two(moves: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(Some(positions_visited::<10>(moves.iter().copied())))
     }
 }
+
+fn main() {
+    Day09::run();
+}
+
+aoc::tests! {
+    solution = Day09,
+    example: 13, 1;
+}
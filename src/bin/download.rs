@@ -2,104 +2,162 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::io::Write;
-use std::path::PathBuf;
-use std::{env::temp_dir, io, process::Command};
-use std::{fs, process};
+use aoc::client::{self, Client};
+use aoc::crypt::{self, Key};
+use aoc::{layout, unlock};
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+use std::{fs, thread};
 
 struct Args {
     day: u8,
-    year: Option<i16>,
+    year: u16,
+    force: bool,
+    wait: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
+        year: args
+            .opt_value_from_fn(["-y", "--year"], layout::parse_year)?
+            .unwrap_or(aoc::DEFAULT_YEAR),
+        force: args.contains(["-f", "--force"]),
+        wait: args.contains(["-w", "--wait"]),
+        day: args.free_from_fn(layout::parse_day)?,
     })
 }
 
-fn remove_file(path: &PathBuf) {
-    #[allow(unused_must_use)]
-    {
-        fs::remove_file(path);
+/// Sleeps until the puzzle unlocks, printing a countdown if stdout is a terminal.
+fn wait_for_unlock(year: u16, day: u8) {
+    let interactive = io::stdout().is_terminal();
+    while let Some(remaining) = unlock::time_until_unlock(year, day) {
+        if interactive {
+            print!("\r\x1b[2KUnlocks in {}", unlock::format_duration(remaining));
+            io::stdout().flush().ok();
+        }
+        thread::sleep(remaining.min(Duration::from_secs(1)));
     }
+    if interactive {
+        println!();
+    }
+    thread::sleep(unlock::jitter());
 }
 
-fn exit_with_status(status: i32, path: &PathBuf) -> ! {
-    remove_file(path);
-    process::exit(status);
+/// Writes the encrypted copy of an input, which is safe to commit.
+fn write_encrypted(key: &Key, input_path: &Path, input: &str) {
+    let path = crypt::encrypted_path(input_path);
+    match aoc::write_file_atomic(&path, &key.encrypt(input)) {
+        Ok(_) => println!("🔒 Wrote encrypted input to \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("could not write encrypted input file: {}", e);
+            process::exit(1);
+        }
+    }
 }
 
 fn main() {
-    // acquire a temp file path to write aoc-cli output to.
-    // aoc-cli expects this file not to be present - delete just in case.
-    let mut tmp_file_path = temp_dir();
-    tmp_file_path.push("aoc_input_tmp");
-    remove_file(&tmp_file_path);
-
     let args = match parse_args() {
         Ok(args) => args,
         Err(e) => {
             eprintln!("Failed to process arguments: {}", e);
-            exit_with_status(1, &tmp_file_path);
+            process::exit(1);
         }
     };
 
-    let day_padded = format!("{:02}", args.day);
-    let input_path = format!("src/inputs/{}.txt", day_padded);
+    let input_path = layout::data_path(args.year, "inputs", args.day);
 
-    // check if aoc binary exists and is callable.
-    if Command::new("aoc").arg("-V").output().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        exit_with_status(1, &tmp_file_path);
-    }
+    let key = match Key::from_env() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    };
 
-    let mut cmd_args = vec![];
+    // `cargo scaffold` creates empty input files, those are fine to replace.
+    let has_input = fs::metadata(&input_path).is_ok_and(|m| m.len() > 0);
+    let has_encrypted = crypt::encrypted_path(&input_path).exists();
+    if has_input && !has_encrypted && !args.force {
+        if let Some(key) = &key {
+            // No need to download again, encrypting the existing input is enough.
+            match fs::read_to_string(&input_path) {
+                Ok(input) => write_encrypted(key, &input_path, &input),
+                Err(e) => {
+                    eprintln!("could not read input file: {}", e);
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+    }
+    if has_input && !args.force {
+        eprintln!(
+            "input file \"{}\" already exists. Pass --force to overwrite it.",
+            input_path.display()
+        );
+        process::exit(1);
+    }
 
-    if let Some(year) = args.year {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    if let Some(remaining) = unlock::time_until_unlock(args.year, args.day) {
+        println!(
+            "Day {} of {} unlocks in {} ({}).",
+            args.day,
+            args.year,
+            unlock::format_duration(remaining),
+            unlock::format_unlock_time(args.year, args.day)
+        );
+        if !args.wait {
+            eprintln!("Pass --wait to download the input as soon as it unlocks.");
+            process::exit(1);
+        }
+        wait_for_unlock(args.year, args.day);
     }
 
-    cmd_args.append(&mut vec![
-        "--input-file".into(),
-        tmp_file_path.to_string_lossy().to_string(),
-        "--day".into(),
-        args.day.to_string(),
-        "download".into(),
-    ]);
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            process::exit(1);
+        }
+    };
 
-    println!("Downloading input with >aoc {}", cmd_args.join(" "));
+    println!("Downloading input for day {}, {}...", args.day, args.year);
 
-    match Command::new("aoc").args(cmd_args).output() {
-        Ok(cmd_output) => {
-            io::stdout()
-                .write_all(&cmd_output.stdout)
-                .expect("could not write cmd stdout to pipe.");
-            io::stderr()
-                .write_all(&cmd_output.stderr)
-                .expect("could not write cmd stderr to pipe.");
-            if !cmd_output.status.success() {
-                exit_with_status(1, &tmp_file_path);
-            }
-        }
+    let input = match client
+        .input(args.year, args.day)
+        .and_then(|input| client::check_input(&input).map(|_| input))
+    {
+        Ok(input) => input,
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
-            exit_with_status(1, &tmp_file_path);
+            eprintln!("could not download input: {:#}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Some(dir) = input_path.parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("could not create input directory: {}", e);
+            process::exit(1);
         }
     }
 
-    match fs::copy(&tmp_file_path, &input_path) {
+    match aoc::write_file_atomic(&input_path, input.as_bytes()) {
         Ok(_) => {
+            if let Some(key) = &key {
+                write_encrypted(key, &input_path, &input);
+            }
             println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            exit_with_status(0, &tmp_file_path);
+            println!(
+                "🎄 Successfully wrote input to \"{}\".",
+                input_path.display()
+            );
         }
         Err(e) => {
-            eprintln!("could not copy downloaded input to input file: {}", e);
-            exit_with_status(1, &tmp_file_path);
+            eprintln!("could not write input file: {}", e);
+            process::exit(1);
         }
     }
 }
@@ -0,0 +1,148 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use aoc::{layout, runner};
+use std::env;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+
+struct Args {
+    day: u8,
+    year: u16,
+    part: u8,
+    iterations: u32,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args
+            .opt_value_from_fn(["-y", "--year"], layout::parse_year)?
+            .unwrap_or(aoc::DEFAULT_YEAR),
+        part: args
+            .opt_value_from_fn(["-p", "--part"], runner::parse_part)?
+            .unwrap_or(1),
+        iterations: args
+            .opt_value_from_fn(["-i", "--iterations"], |s| match s.parse::<u32>() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(format!("invalid number of iterations \"{}\"", s)),
+            })?
+            .unwrap_or(1000),
+        day: args.free_from_fn(layout::parse_day)?,
+    })
+}
+
+/// Whether `program` can be run, i.e. is installed and on the `PATH`.
+fn is_installed(program: &str, version_arg: &str) -> bool {
+    Command::new(program)
+        .arg(version_arg)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+/// Converts a perf recording to folded stacks (one line per stack, as used by flamegraph tools)
+/// if inferno or the FlameGraph scripts are installed.
+fn fold(data: &Path, folded: &Path) -> Option<&'static str> {
+    let collapse = ["inferno-collapse-perf", "stackcollapse-perf.pl"]
+        .into_iter()
+        .find(|tool| is_installed(tool, "--help"))?;
+
+    let script = Command::new("perf")
+        .args(["script", "-i"])
+        .arg(data)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let status = Command::new(collapse)
+        .stdin(script.stdout?)
+        .stdout(File::create(folded).ok()?)
+        .status()
+        .ok()?;
+    status.success().then_some(collapse)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Need to specify a day (as integer). example: `cargo profile-day 7`");
+            process::exit(1);
+        }
+    };
+
+    if !is_installed("perf", "--version") {
+        fail("`perf` is not installed. Install it (e.g. `linux-tools` on Debian and Ubuntu) or profile `cargo solve` with another profiler.");
+    }
+
+    let bin_name = layout::bin_name(args.year, args.day);
+    println!("Building {} in release mode...", bin_name);
+    let built = Command::new("cargo")
+        .args(["build", "--release", "--quiet", "--bin", &bin_name])
+        .status()
+        .is_ok_and(|s| s.success());
+    if !built {
+        fail("could not build the solution, see above.");
+    }
+
+    let target_dir =
+        PathBuf::from(env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into()));
+    let exe = target_dir
+        .join("release")
+        .join(format!("{}{}", bin_name, env::consts::EXE_SUFFIX));
+    let out_dir = target_dir.join("profile");
+    if let Err(e) = fs::create_dir_all(&out_dir) {
+        fail(&format!(
+            "could not create \"{}\": {}",
+            out_dir.display(),
+            e
+        ));
+    }
+    let data = out_dir.join(format!("{}-part{}.perf.data", bin_name, args.part));
+
+    println!(
+        "Profiling part {} with {} iterations...",
+        args.part, args.iterations
+    );
+    // `debug = true` in the release profile provides the DWARF info needed to unwind stacks.
+    let recorded = Command::new("perf")
+        .args(["record", "--call-graph", "dwarf", "-o"])
+        .arg(&data)
+        .arg("--")
+        .arg(&exe)
+        .env("AOC_PART", args.part.to_string())
+        .env("AOC_ITERATIONS", args.iterations.to_string())
+        .status();
+    match recorded {
+        Ok(status) if status.success() => {}
+        Ok(_) => fail("`perf record` failed. You may need to lower `kernel.perf_event_paranoid`."),
+        Err(e) => fail(&format!("could not run perf: {}", e)),
+    }
+
+    println!("---");
+    println!("🎄 Wrote \"{}\".", data.display());
+    println!("   View it with `perf report -i {}`.", data.display());
+
+    let folded = data.with_extension("folded");
+    match fold(&data, &folded) {
+        Some(tool) => println!(
+            "🎄 Wrote folded stacks to \"{}\" using {}.",
+            folded.display(),
+            tool
+        ),
+        None => {
+            println!("   Install inferno (`cargo install inferno`) to also write folded stacks.")
+        }
+    }
+}
@@ -2,113 +2,349 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use aoc::layout;
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
-}
+/// Used unless `.aoc/template.rs` exists. Templates may contain the placeholders
+/// `{{DAY}}`, `{{DAY_PADDED}}`, `{{YEAR}}` and `{{ANSWER_TYPE}}`.
+const MODULE_TEMPLATE: &str = r###"use anyhow::Result;
+use aoc::Solution;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
-}
+pub struct Day{{DAY_PADDED}};
 
-fn main() {
-    let input = &aoc::read_file("inputs", DAY);
-    aoc::solve!(1, part_one, input);
-    aoc::solve!(2, part_two, input);
-}
+impl Solution for Day{{DAY_PADDED}} {
+    const YEAR: u16 = {{YEAR}};
+    const DAY: u8 = {{DAY}};
+    type Parsed = String;
+    type Answer1 = {{ANSWER_TYPE}};
+    type Answer2 = {{ANSWER_TYPE}};
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.to_string())
+    }
 
-    #[test]
-    fn test_part_one() {
-        let input = aoc::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+    fn part_one(_input: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(None)
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = aoc::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+    fn part_two(_input: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(None)
     }
 }
+
+fn main() {
+    Day{{DAY_PADDED}}::run();
+}
+
+aoc::tests! {
+    solution = Day{{DAY_PADDED}},
+    // example: <answer of part one>, <answer of part two>;
+}
 "###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
+const USER_TEMPLATE_PATH: &str = ".aoc/template.rs";
+const REGISTRY_PATH: &str = "src/registry.rs";
+const REGISTRY_START: &str = "pub const DAYS: &[(u16, u8)] = &[";
+
+struct Args {
+    day: u8,
+    year: u16,
+    answer_type: String,
+    force: bool,
+    dry_run: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+    Ok(Args {
+        year: args
+            .opt_value_from_fn(["-y", "--year"], layout::parse_year)?
+            .unwrap_or(aoc::DEFAULT_YEAR),
+        answer_type: args
+            .opt_value_from_str("--answer-type")?
+            .unwrap_or_else(|| "u32".into()),
+        force: args.contains(["-f", "--force"]),
+        dry_run: args.contains("--dry-run"),
+        day: args.free_from_fn(layout::parse_day)?,
+    })
+}
+
+/// Replaces the `{{DAY}}`, `{{DAY_PADDED}}`, `{{YEAR}}` and `{{ANSWER_TYPE}}` placeholders.
+fn render_template(template: &str, args: &Args) -> String {
+    let placeholder = |name: &str| format!("{{{{{}}}}}", name);
+
+    let template = if layout::is_default_year(args.year) {
+        template.to_string()
+    } else {
+        ["read_file", "read_input"]
+            .iter()
+            .fold(template.to_string(), |template, read| {
+                template.replace(
+                    &format!("aoc::{}(", read),
+                    &format!("aoc::{}_for_year({}, ", read, args.year),
+                )
+            })
+    };
+
+    template
+        .replace(&placeholder("DAY_PADDED"), &format!("{:02}", args.day))
+        .replace(&placeholder("ANSWER_TYPE"), &args.answer_type)
+        .replace(&placeholder("YEAR"), &args.year.to_string())
+        .replace(&placeholder("DAY"), &args.day.to_string())
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+fn is_bin_declared(name: &str) -> Result<bool, std::io::Error> {
+    let manifest = fs::read_to_string("Cargo.toml")?;
+    Ok(manifest.contains(&format!("name = \"{}\"", name)))
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Binaries outside of `src/bin/` are not discovered by cargo and need a `[[bin]]` entry.
+fn declare_bin(name: &str, path: &Path) -> Result<(), std::io::Error> {
+    let mut file = OpenOptions::new().append(true).open("Cargo.toml")?;
+    write!(
+        file,
+        "\n[[bin]]\nname = \"{}\"\npath = \"{}\"\n",
+        name,
+        path.display()
+    )
 }
 
-fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+/// Adds `(year, day)` to the `DAYS` list of the registry source, keeping it sorted.
+/// Returns `None` if the day is already registered.
+fn add_registry_entry(source: &str, year: u16, day: u8) -> Option<String> {
+    let start = source.find(REGISTRY_START)? + REGISTRY_START.len();
+    let end = start + source[start..].find("];")?;
+
+    let mut entries: Vec<(u16, u8)> = source[start..end]
+        .split(['(', ')', ',', '\n'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .chunks(2)
+        .filter_map(|pair| Some((pair[0].parse().ok()?, pair.get(1)?.parse().ok()?)))
+        .collect();
+
+    if entries.contains(&(year, day)) {
+        return None;
+    }
+    entries.push((year, day));
+    entries.sort_unstable();
+
+    let list: String = entries
+        .iter()
+        .map(|(year, day)| format!("\n    ({}, {}),", year, day))
+        .collect();
+    Some(format!("{}{}\n{}", &source[..start], list, &source[end..]))
+}
+
+/// Registers the day so that `cargo all` picks it up.
+fn register_day(year: u16, day: u8, dry_run: bool) {
+    let source = match fs::read_to_string(REGISTRY_PATH) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Failed to read registry \"{}\": {}", REGISTRY_PATH, e);
             process::exit(1);
         }
     };
 
-    let day_padded = format!("{:02}", day);
+    if !source.contains(REGISTRY_START) {
+        eprintln!(
+            "Failed to register day: \"{}\" does not contain `{}`",
+            REGISTRY_PATH, REGISTRY_START
+        );
+        process::exit(1);
+    }
+
+    let Some(updated) = add_registry_entry(&source, year, day) else {
+        println!("Day is already registered in \"{}\"", REGISTRY_PATH);
+        return;
+    };
 
-    let input_path = format!("src/inputs/{}.txt", day_padded);
-    let example_path = format!("src/examples/{}.txt", day_padded);
-    let module_path = format!("src/bin/{}.rs", day_padded);
+    if dry_run {
+        println!("Would register day in \"{}\"", REGISTRY_PATH);
+        return;
+    }
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+    match aoc::write_file_atomic(Path::new(REGISTRY_PATH), updated.as_bytes()) {
+        Ok(_) => println!("Registered day in \"{}\"", REGISTRY_PATH),
         Err(e) => {
-            eprintln!("Failed to create module file: {}", e);
+            eprintln!("Failed to update registry: {}", e);
             process::exit(1);
         }
-    };
+    }
+}
+
+/// Creates an empty data file. Existing files are kept, they may already contain puzzle data.
+fn create_data_file(path: &Path, kind: &str, dry_run: bool) {
+    if path.exists() {
+        println!("Kept existing {} file \"{}\"", kind, path.display());
+        return;
+    }
+
+    if dry_run {
+        println!("Would create empty {} file \"{}\"", kind, path.display());
+        return;
+    }
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
         Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created empty {} file \"{}\"", kind, path.display());
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {}", e);
+            eprintln!("Failed to create {} file: {}", kind, e);
             process::exit(1);
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
         Err(e) => {
-            eprintln!("Failed to create input file: {}", e);
+            eprintln!("{}", e);
+            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
             process::exit(1);
         }
+    };
+    let (day, year) = (args.day, args.year);
+
+    let bin_name = layout::bin_name(year, day);
+
+    let input_path = layout::data_path(year, "inputs", day);
+    let example_path = layout::data_path(year, "examples", day);
+    let module_path = layout::module_path(year, day);
+
+    let template = match fs::read_to_string(USER_TEMPLATE_PATH) {
+        Ok(template) => {
+            println!("Using template \"{}\"", USER_TEMPLATE_PATH);
+            template
+        }
+        Err(_) => MODULE_TEMPLATE.to_string(),
+    };
+    let module = render_template(&template, &args);
+
+    let module_exists = module_path.exists();
+    if module_exists && !args.force {
+        eprintln!(
+            "Module file \"{}\" already exists. Pass --force to overwrite it.",
+            module_path.display()
+        );
+        process::exit(1);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    if !args.dry_run {
+        for dir in [&input_path, &example_path, &module_path]
+            .iter()
+            .filter_map(|path| path.parent())
+        {
+            if let Err(e) = fs::create_dir_all(dir) {
+                eprintln!("Failed to create directory \"{}\": {}", dir.display(), e);
+                process::exit(1);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {}", e);
-            process::exit(1);
+    }
+
+    let (action, done) = if module_exists {
+        ("overwrite", "Overwrote")
+    } else {
+        ("create", "Created")
+    };
+    if args.dry_run {
+        println!("Would {} module file \"{}\"", action, module_path.display());
+    } else {
+        match aoc::write_file_atomic(&module_path, module.as_bytes()) {
+            Ok(_) => {
+                println!("{} module file \"{}\"", done, module_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to write module file: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    create_data_file(&input_path, "input", args.dry_run);
+    create_data_file(&example_path, "example", args.dry_run);
+
+    if !layout::is_default_year(year) {
+        match is_bin_declared(&bin_name) {
+            Ok(true) => {}
+            Ok(false) if args.dry_run => {
+                println!("Would declare binary \"{}\" in \"Cargo.toml\"", &bin_name)
+            }
+            Ok(false) => match declare_bin(&bin_name, &module_path) {
+                Ok(_) => println!("Declared binary \"{}\" in \"Cargo.toml\"", &bin_name),
+                Err(e) => {
+                    eprintln!("Failed to declare binary in Cargo.toml: {}", e);
+                    process::exit(1);
+                }
+            },
+            Err(e) => {
+                eprintln!("Failed to read Cargo.toml: {}", e);
+                process::exit(1);
+            }
         }
     }
 
+    register_day(year, day, args.dry_run);
+
+    if args.dry_run {
+        println!("---");
+        println!("🎄 Dry run, no files were changed.");
+        return;
+    }
+
     println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
+    println!("🎄 Type `cargo solve {}` to run your solution.", &bin_name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let args = Args {
+            day: 7,
+            year: 2015,
+            answer_type: "u64".into(),
+            force: false,
+            dry_run: false,
+        };
+        assert_eq!(
+            render_template(
+                "// {{YEAR}} {{DAY_PADDED}}\nfn f() -> {{ANSWER_TYPE}} { aoc::read_file(\"inputs\", {{DAY}}) + &aoc::read_input(\"examples\", {{DAY}}) }",
+                &args
+            ),
+            "// 2015 07\nfn f() -> u64 { aoc::read_file_for_year(2015, \"inputs\", 7) + &aoc::read_input_for_year(2015, \"examples\", 7) }"
+        );
+        assert_eq!(
+            render_template(
+                "struct Day{{DAY_PADDED}};\nconst YEAR: u16 = {{YEAR}};\nconst DAY: u8 = {{DAY}};\nconst DAY_YEAR: u8 = DAY;",
+                &args
+            ),
+            "struct Day07;\nconst YEAR: u16 = 2015;\nconst DAY: u8 = 7;\nconst DAY_YEAR: u8 = DAY;"
+        );
+    }
+
+    #[test]
+    fn test_add_registry_entry() {
+        let source = "pub const DAYS: &[(u16, u8)] = &[\n    (2022, 1),\n    (2022, 3),\n];\n\npub fn f() {}\n";
+        assert_eq!(
+            add_registry_entry(source, 2022, 2).as_deref(),
+            Some("pub const DAYS: &[(u16, u8)] = &[\n    (2022, 1),\n    (2022, 2),\n    (2022, 3),\n];\n\npub fn f() {}\n")
+        );
+        assert_eq!(add_registry_entry(source, 2022, 3), None);
+
+        let empty = "pub const DAYS: &[(u16, u8)] = &[];\n";
+        assert_eq!(
+            add_registry_entry(empty, 2015, 1).as_deref(),
+            Some("pub const DAYS: &[(u16, u8)] = &[\n    (2015, 1),\n];\n")
+        );
+    }
 }
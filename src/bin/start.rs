@@ -0,0 +1,169 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use aoc::client::{self, Client};
+use aoc::layout;
+use std::path::Path;
+use std::process::{self, Command};
+use std::{fs, io};
+
+struct Args {
+    day: u8,
+    year: u16,
+    no_watch: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args
+            .opt_value_from_fn(["-y", "--year"], layout::parse_year)?
+            .unwrap_or(aoc::DEFAULT_YEAR),
+        no_watch: args.contains("--no-watch"),
+        day: args.free_from_fn(layout::parse_day)?,
+    })
+}
+
+enum Outcome {
+    Done(String),
+    Skipped(String),
+    Failed(String),
+}
+
+impl Outcome {
+    fn print(&self, stage: &str) {
+        match self {
+            Outcome::Done(msg) => println!("✔ {}: {}", stage, msg),
+            Outcome::Skipped(msg) => println!("- {}: skipped, {}", stage, msg),
+            Outcome::Failed(msg) => println!("✘ {}: {}", stage, msg),
+        }
+    }
+}
+
+fn is_empty(path: &Path) -> bool {
+    !fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/// Runs one of the other binaries of this crate, e.g. `cargo run --bin scaffold -- 1`.
+fn run_bin(bin: &str, args: &[String]) -> io::Result<bool> {
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--bin", bin, "--"])
+        .args(args)
+        .status()?;
+    Ok(status.success())
+}
+
+fn stage_bin(bin: &str, args: &[String]) -> Outcome {
+    match run_bin(bin, args) {
+        Ok(true) => Outcome::Done(format!("`cargo {}` succeeded", bin)),
+        Ok(false) => Outcome::Failed(format!("`cargo {}` failed, see above", bin)),
+        Err(e) => Outcome::Failed(format!("could not run cargo: {}", e)),
+    }
+}
+
+fn scaffold(args: &Args, common: &[String]) -> Outcome {
+    let module_path = layout::module_path(args.year, args.day);
+    if module_path.exists() {
+        return Outcome::Skipped(format!("\"{}\" already exists", module_path.display()));
+    }
+    stage_bin("scaffold", common)
+}
+
+fn download(args: &Args, common: &[String]) -> Outcome {
+    let input_path = layout::data_path(args.year, "inputs", args.day);
+    if !is_empty(&input_path) {
+        return Outcome::Skipped(format!("\"{}\" already exists", input_path.display()));
+    }
+    let mut download_args = common.to_vec();
+    download_args.push("--wait".into());
+    stage_bin("download", &download_args)
+}
+
+/// Saves the puzzle description and, unless one exists already, the example.
+fn puzzle(args: &Args) -> (Outcome, Outcome) {
+    let html = match Client::from_env().and_then(|client| client.puzzle(args.year, args.day)) {
+        Ok(html) => html,
+        Err(e) => {
+            let msg = format!("could not fetch puzzle: {:#}", e);
+            return (Outcome::Failed(msg.clone()), Outcome::Failed(msg));
+        }
+    };
+
+    let puzzle_path = layout::puzzle_path(args.year, args.day);
+    let description = match write(&puzzle_path, &client::puzzle_to_markdown(&html)) {
+        Ok(_) => Outcome::Done(format!("wrote \"{}\"", puzzle_path.display())),
+        Err(e) => Outcome::Failed(format!("could not write description: {}", e)),
+    };
+
+    let example_path = layout::data_path(args.year, "examples", args.day);
+    let example = if !is_empty(&example_path) {
+        Outcome::Skipped(format!("\"{}\" already exists", example_path.display()))
+    } else {
+        match client::extract_example(&html) {
+            Some(example) => match write(&example_path, &(example + "\n")) {
+                Ok(_) => Outcome::Done(format!("wrote \"{}\"", example_path.display())),
+                Err(e) => Outcome::Failed(format!("could not write example: {}", e)),
+            },
+            None => Outcome::Failed("no example found in the puzzle description".into()),
+        }
+    };
+
+    (description, example)
+}
+
+fn write(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    aoc::write_file_atomic(path, contents.as_bytes())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Need to specify a day (as integer). example: `cargo start 7`");
+            process::exit(1);
+        }
+    };
+
+    let common = vec![
+        args.day.to_string(),
+        "--year".to_string(),
+        args.year.to_string(),
+    ];
+
+    // Without a module there is nothing to work on, the other stages may fail without harm.
+    let scaffolded = scaffold(&args, &common);
+    if let Outcome::Failed(_) = scaffolded {
+        scaffolded.print("scaffold");
+        process::exit(1);
+    }
+    let downloaded = download(&args, &common);
+    let (description, example) = puzzle(&args);
+
+    let outcomes = [
+        ("scaffold", scaffolded),
+        ("download", downloaded),
+        ("description", description),
+        ("example", example),
+    ];
+
+    println!("---");
+    for (stage, outcome) in &outcomes {
+        outcome.print(stage);
+    }
+
+    let bin_name = layout::bin_name(args.year, args.day);
+    if args.no_watch {
+        println!("🎄 Type `cargo solve {}` to run your solution.", bin_name);
+        return;
+    }
+
+    if let Err(e) = run_bin("watch-day", &common) {
+        eprintln!("could not run cargo: {}", e);
+        process::exit(1);
+    }
+}
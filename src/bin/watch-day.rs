@@ -0,0 +1,190 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use aoc::layout;
+use aoc::report::{self, PartResult};
+use aoc::watch::Watcher;
+use std::fs;
+use std::process::{self, Command};
+
+struct Args {
+    day: u8,
+    year: u16,
+    release: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args
+            .opt_value_from_fn(["-y", "--year"], layout::parse_year)?
+            .unwrap_or(aoc::DEFAULT_YEAR),
+        release: args.contains(["-r", "--release"]),
+        day: args.free_from_fn(layout::parse_day)?,
+    })
+}
+
+fn cargo(args: &Args, subcommand: &str, bin_name: &str) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.args([subcommand, "--quiet", "--bin", bin_name]);
+    if args.release {
+        cmd.arg("--release");
+    }
+    cmd
+}
+
+fn show_answer(answer: &Option<String>) -> String {
+    let answer = answer.as_deref().unwrap_or("not solved.");
+    if answer.contains('\n') {
+        format!("\n{}", answer)
+    } else {
+        answer.to_string()
+    }
+}
+
+/// Describes each answer compared to the answer of the same part in the previous run.
+fn diff_answers(previous: Option<&[PartResult]>, current: &[PartResult]) -> Vec<String> {
+    current
+        .iter()
+        .map(|part| {
+            let answer = show_answer(&part.answer);
+            let before = previous.and_then(|p| p.iter().find(|p| p.part == part.part));
+            match before {
+                None => format!("Part {}: {}", part.part, answer),
+                Some(before) if before.answer == part.answer => {
+                    format!("Part {}: {} (unchanged)", part.part, answer)
+                }
+                Some(before) => format!(
+                    "Part {}: {} (changed, was: {})",
+                    part.part,
+                    answer,
+                    show_answer(&before.answer)
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Builds the day, runs its example tests and then runs it against the real input.
+/// Returns the answers of the real input, `None` if the solution did not run.
+fn run(args: &Args, bin_name: &str, previous: Option<&[PartResult]>) -> Option<Vec<PartResult>> {
+    match cargo(args, "build", bin_name).status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            println!("✘ build failed");
+            return None;
+        }
+        Err(e) => {
+            println!("✘ could not run cargo: {}", e);
+            return None;
+        }
+    }
+
+    match cargo(args, "test", bin_name).status() {
+        Ok(status) if status.success() => println!("✔ example tests passed"),
+        _ => println!("✘ example tests failed"),
+    }
+
+    let input_path = layout::data_path(args.year, "inputs", args.day);
+    if !fs::metadata(&input_path).is_ok_and(|m| m.len() > 0) {
+        println!(
+            "- real input: skipped, \"{}\" is empty",
+            input_path.display()
+        );
+        return None;
+    }
+
+    let output = match cargo(args, "run", bin_name).env("NO_COLOR", "1").output() {
+        Ok(output) => output,
+        Err(e) => {
+            println!("✘ could not run cargo: {}", e);
+            return None;
+        }
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        let reason = report::parse_panic(&stderr).unwrap_or_else(|| stderr.trim().to_string());
+        println!("✘ real input: {}", reason);
+        return None;
+    }
+
+    let parts = report::parse_parts(&String::from_utf8_lossy(&output.stdout));
+    println!("✔ real input:");
+    for line in diff_answers(previous, &parts) {
+        println!("  {}", line);
+    }
+    Some(parts)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Need to specify a day (as integer). example: `cargo watch-day 7`");
+            process::exit(1);
+        }
+    };
+
+    let bin_name = layout::bin_name(args.year, args.day);
+    let module_path = layout::module_path(args.year, args.day);
+    if !module_path.exists() {
+        eprintln!(
+            "Module file \"{}\" does not exist. Run `cargo scaffold {}` first.",
+            module_path.display(),
+            args.day
+        );
+        process::exit(1);
+    }
+
+    let mut watcher = Watcher::new(vec![
+        module_path,
+        layout::data_path(args.year, "examples", args.day),
+        layout::data_path(args.year, "inputs", args.day),
+    ]);
+    let mut previous: Option<Vec<PartResult>> = None;
+
+    loop {
+        println!("---");
+        if let Some(parts) = run(&args, &bin_name, previous.as_deref()) {
+            previous = Some(parts);
+        }
+        println!("🎄 Watching for changes, press Ctrl-C to stop.");
+        for path in watcher.wait() {
+            println!("Changed: \"{}\"", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            answer: answer.map(String::from),
+            elapsed: None,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_answers() {
+        let first = [part(1, Some("24000")), part(2, None)];
+        assert_eq!(
+            diff_answers(None, &first),
+            ["Part 1: 24000", "Part 2: not solved."]
+        );
+
+        let second = [part(1, Some("24000")), part(2, Some("45000"))];
+        assert_eq!(
+            diff_answers(Some(&first), &second),
+            [
+                "Part 1: 24000 (unchanged)",
+                "Part 2: 45000 (changed, was: not solved.)"
+            ]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Advent of Code asks automated tools to identify themselves.
+/// See: https://www.reddit.com/r/adventofcode/wiki/faqs/automation
+const USER_AGENT: &str = concat!(
+    "github.com/ChiefMilesEdgeworth/advent-of-code-2022 (aoc ",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+/// `~/.adventofcode.session`, the same file used by aoc-cli.
+fn session_file() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+}
+
+/// Reads the session cookie from `AOC_SESSION` or `~/.adventofcode.session`.
+pub fn session() -> Result<String> {
+    if let Some(session) = env::var("AOC_SESSION")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = session_file().context("could not determine home directory")?;
+    let session = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "no session cookie found. Set AOC_SESSION or create \"{}\"",
+            path.display()
+        )
+    })?;
+
+    match session.trim() {
+        "" => bail!("session file \"{}\" is empty", path.display()),
+        session => Ok(session.to_string()),
+    }
+}
+
+/// Error pages and login redirects are served as HTML, puzzle inputs never are.
+pub fn looks_like_html(s: &str) -> bool {
+    let start = s.trim_start().get(..15).unwrap_or(s.trim_start());
+    let start = start.to_ascii_lowercase();
+    start.starts_with("<!doctype html") || start.starts_with("<html")
+}
+
+/// Checks that a downloaded input is plausible before it is written to disk.
+pub fn check_input(input: &str) -> Result<()> {
+    if input.trim().is_empty() {
+        bail!("downloaded input is empty");
+    }
+    if looks_like_html(input) {
+        bail!("downloaded input looks like an HTML page (is the session cookie valid?)");
+    }
+    Ok(())
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Creates a client for adventofcode.com (or `AOC_BASE_URL`, if set) using the configured session.
+    pub fn from_env() -> Result<Self> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| BASE_URL.into());
+        Ok(Client::new(&base_url, &session()?))
+    }
+
+    fn get(&self, path: &str) -> Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("could not read response from {}", url)),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                let hint = match code {
+                    400 | 500 => " (is the session cookie valid?)",
+                    404 => " (is the puzzle unlocked yet?)",
+                    _ => "",
+                };
+                let message = body.lines().next().unwrap_or_default();
+                bail!(
+                    "{} returned status {}{}: {}",
+                    url,
+                    code,
+                    hint,
+                    message.trim()
+                )
+            }
+            Err(e) => Err(e).with_context(|| format!("request to {} failed", url)),
+        }
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// The HTML of the puzzle page. Part two is only included once part one is solved.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String> {
+        self.get(&format!("/{}/day/{}", year, day))
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_tags(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_tag = false;
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&out)
+}
+
+/// The `<article class="day-desc">` blocks of a puzzle page, one per unlocked part.
+fn articles(html: &str) -> Vec<&str> {
+    html.split("<article class=\"day-desc\">")
+        .skip(1)
+        .filter_map(|s| s.split_once("</article>").map(|(article, _)| article))
+        .collect()
+}
+
+/// Converts the puzzle description of a puzzle page to markdown.
+/// Only covers the handful of tags used on adventofcode.com.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    let mut md = articles(html)
+        .join("\n")
+        .replace("\n</code></pre>", "</code></pre>");
+    for (tag, replacement) in [
+        ("<h2>", "## "),
+        ("</h2>", "\n\n"),
+        ("<p>", ""),
+        ("</p>", "\n\n"),
+        ("<pre><code>", "```\n"),
+        ("</code></pre>", "\n```\n\n"),
+        ("<code>", "`"),
+        ("</code>", "`"),
+        ("<em class=\"star\">", "**"),
+        ("<em>", "**"),
+        ("</em>", "**"),
+        ("<ul>", ""),
+        ("</ul>", "\n"),
+        ("<li>", "- "),
+        ("</li>", "\n"),
+    ] {
+        md = md.replace(tag, replacement);
+    }
+
+    // Tags are separated by newlines in the page source, which leaves runs of blank lines
+    // behind. Collapse those, except inside code blocks.
+    let mut out = String::new();
+    let (mut in_code, mut blank) = (false, false);
+    for line in strip_tags(&md).trim().lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+        }
+        if !in_code && blank && line.is_empty() {
+            continue;
+        }
+        blank = line.is_empty();
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Finds the example input of a puzzle page: the first code block following a paragraph
+/// that mentions an example, or the first code block if no paragraph does.
+pub fn extract_example(html: &str) -> Option<String> {
+    let article = *articles(html).first()?;
+    let blocks: Vec<(&str, &str)> = article
+        .split("<pre><code>")
+        .collect::<Vec<_>>()
+        .windows(2)
+        .filter_map(|w| Some((w[0], w[1].split_once("</code></pre>")?.0)))
+        .collect();
+
+    let (_, block) = blocks
+        .iter()
+        .find(|(before, _)| {
+            let paragraph = before.rsplit("<p>").next().unwrap_or_default();
+            paragraph.to_lowercase().contains("example")
+        })
+        .or_else(|| blocks.first())?;
+
+    Some(strip_tags(block).trim_end_matches('\n').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves a single canned response and returns the request head it received.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                request.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_input() {
+        let (url, server) = serve_once("200 OK", "1000\n2000\n");
+        let input = Client::new(&url, "abc123").input(2022, 1).unwrap();
+        let request = server.join().unwrap().to_lowercase();
+
+        assert_eq!(input, "1000\n2000\n");
+        assert!(request.starts_with("get /2022/day/1/input http/1.1"));
+        assert!(request.contains("cookie: session=abc123"));
+        assert!(request.contains("user-agent: github.com/chiefmilesedgeworth"));
+    }
+
+    #[test]
+    fn test_check_input() {
+        assert!(check_input("1000\n2000\n").is_ok());
+        assert!(check_input(" \n").is_err());
+        assert!(check_input("<!DOCTYPE html>\n<html lang=\"en-us\">").is_err());
+    }
+
+    const PUZZLE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>The jungle must be too overgrown.</p>
+<p>Just in case, here is <em>something</em>:</p>
+<pre><code>not this</code></pre>
+<p>For example, suppose the Elves finish writing their items' <code>Calories</code>:</p>
+<pre><code>1000
+<em>2000</em>
+
+&lt;3000&gt;
+</code></pre>
+<ul>
+<li>The first Elf.</li>
+</ul>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract_example() {
+        assert_eq!(
+            extract_example(PUZZLE).as_deref(),
+            Some("1000\n2000\n\n<3000>")
+        );
+        assert_eq!(extract_example("<main></main>"), None);
+    }
+
+    #[test]
+    fn test_puzzle_to_markdown() {
+        let md = puzzle_to_markdown(PUZZLE);
+        assert!(md.starts_with("## --- Day 1: Calorie Counting ---\n\nThe jungle"));
+        assert!(md.contains("here is **something**:"));
+        assert!(md.contains("items' `Calories`:\n\n```\n1000\n**2000**\n\n<3000>\n```"));
+        assert!(md.contains("- The first Elf.\n"));
+    }
+
+    #[test]
+    fn test_input_error() {
+        let (url, server) = serve_once(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let err = Client::new(&url, "abc123").input(2022, 25).unwrap_err();
+        server.join().unwrap();
+
+        assert!(err.to_string().contains("404"));
+    }
+}
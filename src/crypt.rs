@@ -0,0 +1,130 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use anyhow::{bail, Context, Result};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Read if `AOC_INPUT_KEY` is not set. Never commit this file.
+pub const KEY_FILE: &str = ".aoc/input.key";
+
+const MAGIC: &[u8] = b"AOC1";
+const NONCE_LEN: usize = 12;
+
+/// Encrypted inputs live next to the plain ones, e.g. `src/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".enc");
+    path.with_file_name(name)
+}
+
+pub struct Key {
+    cipher: ChaCha20Poly1305,
+    bytes: [u8; 32],
+}
+
+impl Key {
+    /// Any passphrase works, it is hashed to a 256-bit key.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        let bytes: [u8; 32] = Sha256::digest(passphrase.trim().as_bytes()).into();
+        Key {
+            cipher: ChaCha20Poly1305::new(&bytes.into()),
+            bytes,
+        }
+    }
+
+    /// Reads the key from `AOC_INPUT_KEY` or `.aoc/input.key`, `None` if neither is set.
+    pub fn from_env() -> Result<Option<Self>> {
+        if let Some(passphrase) = env::var("AOC_INPUT_KEY")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+        {
+            return Ok(Some(Key::from_passphrase(&passphrase)));
+        }
+        if !Path::new(KEY_FILE).exists() {
+            return Ok(None);
+        }
+        let passphrase = fs::read_to_string(KEY_FILE)
+            .with_context(|| format!("could not read key file \"{}\"", KEY_FILE))?;
+        if passphrase.trim().is_empty() {
+            bail!("key file \"{}\" is empty", KEY_FILE);
+        }
+        Ok(Some(Key::from_passphrase(&passphrase)))
+    }
+
+    /// The nonce is derived from the key and the input, so encrypting the same input twice
+    /// gives the same file and re-downloads don't show up as changes in git.
+    pub fn encrypt(&self, plaintext: &str) -> Vec<u8> {
+        let digest = Sha256::new()
+            .chain_update(self.bytes)
+            .chain_update(plaintext.as_bytes())
+            .finalize();
+        let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+        let ciphertext = self
+            .cipher
+            .encrypt(nonce, plaintext.as_bytes())
+            .expect("encryption cannot fail for inputs of this size");
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<String> {
+        let Some(data) = data.strip_prefix(MAGIC).filter(|d| d.len() >= NONCE_LEN) else {
+            bail!("not an encrypted input file");
+        };
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .ok()
+            .context("could not decrypt input, is the key correct?")?;
+        String::from_utf8(plaintext).context("decrypted input is not valid UTF-8")
+    }
+}
+
+/// Reads and decrypts the encrypted copy of `path`.
+pub fn read_encrypted(path: &Path) -> Result<String> {
+    decrypt_copy(path, &fs::read(encrypted_path(path))?)
+}
+
+/// Decrypts `data`, the encrypted copy of `path`, with the configured key.
+pub fn decrypt_copy(path: &Path, data: &[u8]) -> Result<String> {
+    let Some(key) = Key::from_env()? else {
+        bail!(
+            "\"{}\" is encrypted. Set AOC_INPUT_KEY or create \"{}\"",
+            encrypted_path(path).display(),
+            KEY_FILE
+        );
+    };
+    key.decrypt(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let key = Key::from_passphrase("correct horse battery staple");
+        let encrypted = key.encrypt("1000\n2000\n");
+        assert!(encrypted.starts_with(MAGIC));
+        assert_eq!(encrypted, key.encrypt("1000\n2000\n"));
+        assert_eq!(key.decrypt(&encrypted).unwrap(), "1000\n2000\n");
+
+        let other = Key::from_passphrase("incorrect");
+        assert!(other.decrypt(&encrypted).is_err());
+        assert!(key.decrypt(b"1000\n2000\n").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_path() {
+        assert_eq!(
+            encrypted_path(Path::new("src/inputs/01.txt")),
+            PathBuf::from("src/inputs/01.txt.enc")
+        );
+    }
+}
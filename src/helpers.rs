@@ -2,5 +2,194 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use aoc::helpers::example_fn;`.
  */
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 
 pub fn test() {}
+
+/// A non-empty, closed interval of integers, e.g. `2-4`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Interval(RangeInclusive<i64>);
+
+impl Interval {
+    /// Panics if `start > end`.
+    pub fn new(start: i64, end: i64) -> Self {
+        assert!(start <= end, "empty interval {}-{}", start, end);
+        Interval(start..=end)
+    }
+
+    pub fn start(&self) -> i64 {
+        *self.0.start()
+    }
+
+    pub fn end(&self) -> i64 {
+        *self.0.end()
+    }
+
+    /// The number of integers in the interval, saturating at `u64::MAX` for `i64::MIN..=i64::MAX`.
+    pub fn len(&self) -> u64 {
+        self.end().abs_diff(self.start()).saturating_add(1)
+    }
+
+    /// Always false, an interval contains at least its start.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start() <= other.start() && other.end() <= self.end()
+    }
+
+    pub fn contains_point(&self, point: i64) -> bool {
+        self.0.contains(&point)
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start() <= other.end() && other.start() <= self.end()
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start().max(other.start()), self.end().min(other.end())))
+    }
+
+    /// The union of two intervals that overlap or are adjacent, e.g. `1-2` and `3-4`.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let touches = self.start() <= other.end().saturating_add(1)
+            && other.start() <= self.end().saturating_add(1);
+        touches.then(|| Interval::new(self.start().min(other.start()), self.end().max(other.end())))
+    }
+}
+
+impl From<Interval> for RangeInclusive<i64> {
+    fn from(interval: Interval) -> Self {
+        interval.0
+    }
+}
+
+/// Parses `start-end`, negative numbers are allowed, e.g. `-3--1`.
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid interval \"{}\"", s);
+        let split = s
+            .get(1..)
+            .and_then(|rest| rest.find('-'))
+            .ok_or_else(invalid)?
+            + 1;
+        let start = s[..split].parse().map_err(|_| invalid())?;
+        let end = s[split + 1..].parse().map_err(|_| invalid())?;
+        if start > end {
+            return Err(invalid());
+        }
+        Ok(Interval::new(start, end))
+    }
+}
+
+/// A set of integers, stored as sorted, disjoint intervals.
+/// Overlapping and adjacent intervals are merged when inserted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        // Intervals before `first` end too early to merge, the ones from `last` on start too late.
+        let first = self
+            .intervals
+            .partition_point(|i| i.end().saturating_add(1) < interval.start());
+        let last = self
+            .intervals
+            .partition_point(|i| i.start() <= interval.end().saturating_add(1));
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, i| merged.union(i).unwrap());
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// The number of integers in the set, saturating at `u64::MAX`.
+    pub fn len(&self) -> u64 {
+        self.intervals
+            .iter()
+            .map(Interval::len)
+            .fold(0, u64::saturating_add)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains_point(&self, point: i64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end() < point);
+        self.intervals
+            .get(i)
+            .is_some_and(|i| i.contains_point(point))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a: Interval = "2-6".parse().unwrap();
+        let b: Interval = "4-8".parse().unwrap();
+        assert_eq!(a.len(), 5);
+        assert!(a.overlaps(&b) && !a.contains(&b));
+        assert!(a.contains(&Interval::new(3, 6)));
+        assert_eq!(a.intersection(&b), Some(Interval::new(4, 6)));
+        assert_eq!(a.union(&b), Some(Interval::new(2, 8)));
+        assert_eq!(a.union(&Interval::new(7, 9)), Some(Interval::new(2, 9)));
+        assert_eq!(a.union(&Interval::new(8, 9)), None);
+        assert_eq!(a.intersection(&Interval::new(7, 9)), None);
+
+        assert_eq!("-3--1".parse(), Ok(Interval::new(-3, -1)));
+        assert!("4-2".parse::<Interval>().is_err());
+        assert!("4".parse::<Interval>().is_err());
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let set: IntervalSet = [(10, 12), (1, 3), (5, 6), (4, 4), (11, 20), (30, 30)]
+            .into_iter()
+            .map(|(start, end)| Interval::new(start, end))
+            .collect();
+        assert_eq!(
+            set.iter().cloned().collect::<Vec<_>>(),
+            [
+                Interval::new(1, 6),
+                Interval::new(10, 20),
+                Interval::new(30, 30)
+            ]
+        );
+        assert_eq!(set.len(), 6 + 11 + 1);
+        assert!(set.contains_point(15) && !set.contains_point(7));
+
+        let set: IntervalSet = [Interval::new(i64::MIN, -1), Interval::new(1, i64::MAX)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), u64::MAX);
+    }
+}
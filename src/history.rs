@@ -0,0 +1,333 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::report::{round_ms, DayResult};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `cargo all` appends the timings of every run to this file.
+pub const HISTORY_PATH: &str = ".aoc/history.csv";
+
+const HEADER: &str = "rev,date,year,day,part,median_ms";
+
+/// The median timing of one part in one run of `cargo all`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// Short git revision, suffixed with `-dirty` if the working tree had changes.
+    pub rev: String,
+    pub date: String,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub median_ms: f64,
+}
+
+/// Date for a day count since 1970-01-01, the inverse of `days_from_civil` in `unlock.rs`.
+/// See: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Formats a time as e.g. `2022-12-08T10:11:12Z`.
+pub fn format_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()) as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let secs = secs.rem_euclid(86400);
+    format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+/// The current git revision, `unknown` outside of a git repository.
+pub fn git_revision() -> String {
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(rev) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", rev)
+        }
+        Some(rev) => rev,
+        None => "unknown".into(),
+    }
+}
+
+/// Resolves e.g. `HEAD~1`, a branch or a tag to the full hash of its commit.
+/// `last` and revisions unknown to git are returned as is.
+pub fn resolve_revision(rev: &str) -> String {
+    if rev == "last" {
+        return rev.into();
+    }
+    git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{}^{{commit}}", rev),
+    ])
+    .unwrap_or_else(|| rev.into())
+}
+
+pub fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0_f64;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2_f64
+    } else {
+        values[mid]
+    }
+}
+
+/// Formats milliseconds the same way `solve!` formats timings, e.g. `1.45ms`.
+pub fn format_ms(ms: f64) -> String {
+    format!("{:.2?}", Duration::from_secs_f64(ms.max(0_f64) / 1000_f64))
+}
+
+/// Entries for every solved part of `results`.
+pub fn entries(results: &[DayResult], year: u16, rev: &str, date: &str) -> Vec<Entry> {
+    results
+        .iter()
+        .flat_map(|day| {
+            day.parts
+                .iter()
+                .filter(|part| part.answer.is_some() && part.elapsed.is_some())
+                .map(|part| Entry {
+                    rev: rev.to_string(),
+                    date: date.to_string(),
+                    year,
+                    day: day.day,
+                    part: part.part,
+                    median_ms: part.elapsed_ms(),
+                })
+        })
+        .collect()
+}
+
+/// Parses a history file, skipping lines that are not valid entries.
+pub fn parse(contents: &str) -> Vec<Entry> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let [rev, date, year, day, part, median_ms] = fields[..] else {
+                return None;
+            };
+            Some(Entry {
+                rev: rev.to_string(),
+                date: date.to_string(),
+                year: year.parse().ok()?,
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                median_ms: median_ms.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+pub fn load(path: &Path) -> Vec<Entry> {
+    fs::read_to_string(path).map_or(vec![], |contents| parse(&contents))
+}
+
+pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let is_new = !path.exists();
+    let mut out = String::new();
+    if is_new {
+        writeln!(out, "{}", HEADER).unwrap();
+    }
+    for e in entries {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            e.rev,
+            e.date,
+            e.year,
+            e.day,
+            e.part,
+            round_ms(e.median_ms)
+        )
+        .unwrap();
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(out.as_bytes())
+}
+
+/// The most recent timing of each part of `year`, either overall (`rev` is `last`) or at `rev`.
+/// `rev` may be a prefix of the recorded short hashes or a full hash, see `resolve_revision`.
+pub fn baseline<'a>(history: &'a [Entry], year: u16, rev: &str) -> BTreeMap<(u8, u8), &'a Entry> {
+    let matches = |recorded: &str| {
+        let recorded = recorded.trim_end_matches("-dirty");
+        rev == "last" || recorded.starts_with(rev) || rev.starts_with(recorded)
+    };
+    history
+        .iter()
+        .filter(|e| e.year == year && matches(&e.rev))
+        .map(|e| ((e.day, e.part), e))
+        .collect()
+}
+
+/// Renders a table of per-part speedups and slowdowns.
+pub fn compare(baseline: &BTreeMap<(u8, u8), &Entry>, current: &[Entry]) -> String {
+    let mut out = format!(
+        "{:<4} {:>4} {:>12} {:>12}  {}\n",
+        "Day", "Part", "Before", "After", "Change"
+    );
+    for entry in current {
+        let before = baseline.get(&(entry.day, entry.part));
+        // Timings below a microsecond are recorded as zero, there is nothing to compare.
+        let change = match before.map(|b| (b.median_ms, entry.median_ms)) {
+            Some((before, after)) if before == 0_f64 || after == 0_f64 => "-".into(),
+            Some((before, after)) if before >= after => {
+                format!("{:.2}x faster", before / after)
+            }
+            Some((before, after)) => format!("{:.2}x slower", after / before),
+            None => "-".into(),
+        };
+        writeln!(
+            out,
+            "{:<4} {:>4} {:>12} {:>12}  {}",
+            format!("{:02}", entry.day),
+            entry.part,
+            before.map_or("-".into(), |b| format_ms(b.median_ms)),
+            format_ms(entry.median_ms),
+            change
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    fn entry(rev: &str, day: u8, part: u8, median_ms: f64) -> Entry {
+        Entry {
+            rev: rev.into(),
+            date: "2022-12-08T10:11:12Z".into(),
+            year: 2022,
+            day,
+            part,
+            median_ms,
+        }
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_date(UNIX_EPOCH + Duration::from_secs(1670494272)),
+            "2022-12-08T10:11:12Z"
+        );
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&mut [4.0, 1.0, 2.0, 3.0]), 2.5);
+        assert_eq!(median(&mut []), 0.0);
+    }
+
+    #[test]
+    fn test_parse() {
+        let history = parse(&format!(
+            "{}\nabc1234,2022-12-08T10:11:12Z,2022,8,1,1.5\nbroken\n",
+            HEADER
+        ));
+        assert_eq!(history, vec![entry("abc1234", 8, 1, 1.5)]);
+    }
+
+    #[test]
+    fn test_append() {
+        let dir = env::temp_dir().join(format!("aoc_history_test_{}", process::id()));
+        let path = dir.join("history.csv");
+        append(&path, &[entry("abc1234", 8, 1, 0.0020499999999999997)]).unwrap();
+        append(&path, &[entry("abc1234", 8, 2, 0.000074)]).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains(",8,1,0.00205\n"));
+        assert_eq!(
+            load(&path),
+            [
+                entry("abc1234", 8, 1, 0.00205),
+                entry("abc1234", 8, 2, 0.000074)
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = [
+            entry("abc1234", 8, 1, 3.0),
+            entry("abc1234", 8, 2, 1.0),
+            entry("def5678-dirty", 8, 1, 2.0),
+            entry("def5678-dirty", 9, 2, 1.0),
+        ];
+        let current = [
+            entry("", 8, 1, 1.0),
+            entry("", 8, 2, 2.0),
+            entry("", 9, 1, 1.0),
+            entry("", 9, 2, 0.0),
+        ];
+
+        let last = baseline(&history, 2022, "last");
+        assert_eq!(last[&(8, 1)].rev, "def5678-dirty");
+        assert_eq!(baseline(&history, 2022, "abc")[&(8, 1)].rev, "abc1234");
+        assert_eq!(
+            baseline(&history, 2022, "def5678a9b0c1d2e3f4a5b6c7d8e9f0a1b2c3d4")[&(8, 1)].rev,
+            "def5678-dirty"
+        );
+        assert!(baseline(&history, 2022, "0123456").is_empty());
+        assert!(baseline(&history, 2015, "last").is_empty());
+
+        assert_eq!(
+            compare(&last, &current),
+            [
+                "Day  Part       Before        After  Change",
+                "08      1       2.00ms       1.00ms  2.00x faster",
+                "08      2       1.00ms       2.00ms  2.00x slower",
+                "09      1            -       1.00ms  -",
+                "09      2       1.00ms       0.00ns  -",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::DEFAULT_YEAR;
+use std::path::PathBuf;
+
+/// Solutions for the default year keep the flat layout:
+///
+/// - `src/bin/01.rs` (binary `01`)
+/// - `src/inputs/01.txt`, `src/examples/01.txt`
+///
+/// Solutions for other years live in a directory per year:
+///
+/// - `src/y2021/d01.rs` (binary `2021-01`, declared in `Cargo.toml`)
+/// - `src/y2021/inputs/01.txt`, `src/y2021/examples/01.txt`
+pub fn is_default_year(year: u16) -> bool {
+    year == DEFAULT_YEAR
+}
+
+/// Directory containing the solutions of `year`, relative to the project root.
+pub fn year_dir(year: u16) -> PathBuf {
+    if is_default_year(year) {
+        PathBuf::from("src")
+    } else {
+        PathBuf::from("src").join(format!("y{}", year))
+    }
+}
+
+pub fn bin_name(year: u16, day: u8) -> String {
+    if is_default_year(year) {
+        format!("{:02}", day)
+    } else {
+        format!("{}-{:02}", year, day)
+    }
+}
+
+pub fn module_path(year: u16, day: u8) -> PathBuf {
+    if is_default_year(year) {
+        year_dir(year).join("bin").join(format!("{:02}.rs", day))
+    } else {
+        year_dir(year).join(format!("d{:02}.rs", day))
+    }
+}
+
+/// Path of an input or example file, e.g. `data_path(2021, "inputs", 1)`.
+pub fn data_path(year: u16, folder: &str, day: u8) -> PathBuf {
+    year_dir(year).join(folder).join(format!("{:02}.txt", day))
+}
+
+/// Puzzle description saved by `cargo start`, e.g. `src/puzzles/01.md`.
+pub fn puzzle_path(year: u16, day: u8) -> PathBuf {
+    year_dir(year)
+        .join("puzzles")
+        .join(format!("{:02}.md", day))
+}
+
+pub fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day \"{}\", expected 1-25", s.trim())),
+    }
+}
+
+/// The first Advent of Code took place in 2015.
+pub fn parse_year(s: &str) -> Result<u16, String> {
+    match s.parse::<u16>() {
+        Ok(year) if year >= 2015 => Ok(year),
+        _ => Err(format!("invalid year \"{}\", expected 2015 or later", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(bin_name(DEFAULT_YEAR, 1), "01");
+        assert_eq!(bin_name(2015, 1), "2015-01");
+        assert_eq!(module_path(DEFAULT_YEAR, 7), PathBuf::from("src/bin/07.rs"));
+        assert_eq!(module_path(2015, 7), PathBuf::from("src/y2015/d07.rs"));
+        assert_eq!(
+            data_path(DEFAULT_YEAR, "inputs", 7),
+            PathBuf::from("src/inputs/07.txt")
+        );
+        assert_eq!(
+            data_path(2015, "examples", 7),
+            PathBuf::from("src/y2015/examples/07.txt")
+        );
+        assert_eq!(
+            puzzle_path(2015, 7),
+            PathBuf::from("src/y2015/puzzles/07.md")
+        );
+    }
+}
@@ -4,77 +4,308 @@
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
 use std::env;
+use std::fmt::Display;
 use std::fs;
+use std::hint;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
+pub mod client;
+pub mod crypt;
 pub mod helpers;
+pub mod history;
+pub mod layout;
+pub mod memory;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod solution;
+#[doc(hidden)]
+pub mod testing;
+pub mod unlock;
+pub mod watch;
+
+pub use solution::Solution;
+
+/// `cargo all --features count-allocations` reports the allocations of each part, see `memory.rs`.
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+/// Solutions for this year use the flat `src/bin/`, `src/inputs/` layout.
+pub const DEFAULT_YEAR: u16 = 2022;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Colors are disabled if `NO_COLOR` is set or stdout is not a terminal.
+/// See: https://no-color.org
+pub fn colors_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && io::stdout().is_terminal()
+    })
+}
+
+/// Returns `code` if colors are enabled, an empty string otherwise.
+pub fn ansi(code: &'static str) -> &'static str {
+    if colors_enabled() {
+        code
+    } else {
+        ""
+    }
+}
+
+/// `cargo all --part <n>` sets `AOC_PART` to only run one part of each solution.
+pub fn part_enabled(part: u8) -> bool {
+    env::var("AOC_PART").map_or(true, |p| p == part.to_string())
+}
+
+/// `cargo profile-day --iterations <n>` sets `AOC_ITERATIONS` to call each part `n` times in a row.
+pub fn iterations() -> u32 {
+    env::var("AOC_ITERATIONS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(1)
+}
+
+/// `cargo all --jobs <n> --serial-timing` sets `AOC_TIMING_LOCK` to a shared lock file.
+/// Holding the lock while timing keeps concurrently running solutions from skewing measurements.
+pub fn timing_lock() -> Option<fs::File> {
+    let path = env::var_os("AOC_TIMING_LOCK")?;
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .ok()?;
+    file.lock().ok()?;
+    Some(file)
+}
+
+/// Runs one part and prints its answer in the format `cargo all` parses.
+/// Errors are printed to stderr and end the process with a non-zero exit code.
+pub fn print_part<T: Display>(part: u8, func: impl Fn() -> anyhow::Result<Option<T>>) {
+    if !part_enabled(part) {
+        return;
+    }
+    println!("🎄 {}Part {}{} 🎄", ansi(ANSI_BOLD), part, ansi(ANSI_RESET));
+
+    let lock = timing_lock();
+    let iterations = iterations();
+    memory::reset();
+    let timer = Instant::now();
+    let mut result = func();
+    for _ in 1..iterations {
+        result = hint::black_box(func());
+    }
+    // With `AOC_ITERATIONS`, this is the mean time of a single call.
+    let elapsed = timer.elapsed() / iterations;
+    let memory = memory::stats();
+    drop(lock);
+
+    match result {
+        Ok(Some(result)) => println!(
+            "{} {}(elapsed: {:.2?}{}){}",
+            result,
+            ansi(ANSI_ITALIC),
+            elapsed,
+            memory.map_or(String::new(), |m| format!(", {}", m)),
+            ansi(ANSI_RESET)
+        ),
+        Ok(None) => println!("not solved."),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
-        use aoc::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
-        }
-
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        let input = $input;
+        aoc::print_part($part, || Ok($solver(std::hint::black_box(input))));
     }};
 }
 
+/// Reads an input or example file as is. Prefer `read_input`, which also cleans up line endings.
 pub fn read_file(folder: &str, day: u8) -> String {
+    read_file_for_year(DEFAULT_YEAR, folder, day)
+}
+
+pub fn read_file_for_year(year: u16, folder: &str, day: u8) -> String {
+    let path = layout::data_path(year, folder, day);
+    if let Some(f) = embedded_file(&path) {
+        warn_suspicious_input(&path, &f);
+        return f;
+    }
+
     let cwd = env::current_dir().unwrap();
 
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
+    let filepath = cwd.join(path);
+
+    // Inputs may be committed in encrypted form only, see `crypt.rs`.
+    let f = if !filepath.exists() && crypt::encrypted_path(&filepath).exists() {
+        crypt::read_encrypted(&filepath).unwrap_or_else(|e| panic!("{:#}", e))
+    } else {
+        fs::read_to_string(&filepath).expect("could not open input file")
+    };
+    warn_suspicious_input(&filepath, &f);
+    f
+}
+
+#[cfg(feature = "embed-inputs")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+}
+
+/// With the `embed-inputs` feature, files that existed at build time are compiled into the binary.
+/// Encrypted inputs are embedded as is and decrypted when read, so the key is still needed at runtime.
+/// Files missing at build time are read from disk as usual.
+#[cfg(feature = "embed-inputs")]
+fn embedded_file(path: &Path) -> Option<String> {
+    if let Some((_, contents)) = embedded::FILES
+        .iter()
+        .find(|(embedded, _)| Path::new(embedded) == path)
+    {
+        return Some(contents.to_string());
+    }
+    embedded::ENCRYPTED
+        .iter()
+        .find(|(embedded, _)| Path::new(embedded) == path)
+        .map(|(_, data)| crypt::decrypt_copy(path, data).unwrap_or_else(|e| panic!("{:#}", e)))
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn embedded_file(_path: &Path) -> Option<String> {
+    None
+}
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+/// Reads an input or example file with all normalisations of `Normalize::default()` applied.
+pub fn read_input(folder: &str, day: u8) -> String {
+    read_input_for_year(DEFAULT_YEAR, folder, day)
+}
+
+pub fn read_input_for_year(year: u16, folder: &str, day: u8) -> String {
+    read_input_with(year, folder, day, Normalize::default())
+}
+
+/// e.g. `read_input_with(2022, "inputs", 5, Normalize { trim_end: false, ..Default::default() })`
+pub fn read_input_with(year: u16, folder: &str, day: u8, options: Normalize) -> String {
+    normalize(&read_file_for_year(year, folder, day), options)
+}
+
+/// Clean-ups applied by `read_input`. All of them are enabled by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Normalize {
+    /// Removes a leading UTF-8 byte order mark.
+    pub strip_bom: bool,
+    /// Converts `\r\n` line endings to `\n`.
+    pub crlf: bool,
+    /// Removes trailing whitespace, including the final newline.
+    pub trim_end: bool,
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Normalize {
+            strip_bom: true,
+            crlf: true,
+            trim_end: true,
+        }
+    }
+}
+
+pub fn normalize(input: &str, options: Normalize) -> String {
+    let mut input = input;
+    if options.strip_bom {
+        input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    }
+    if options.trim_end {
+        input = input.trim_end();
+    }
+    if options.crlf {
+        input.replace("\r\n", "\n")
+    } else {
+        input.to_string()
+    }
+}
+
+/// Warns about input files that are almost certainly not what the solution expects.
+fn warn_suspicious_input(path: &Path, contents: &str) {
+    if contents.trim().is_empty() {
+        eprintln!("warning: \"{}\" is empty", path.display());
+    } else if client::looks_like_html(contents) {
+        eprintln!(
+            "warning: \"{}\" looks like an HTML page, try downloading it again",
+            path.display()
+        );
+    }
+}
+
+/// Writes `contents` to a uniquely named temporary file next to `path` and renames it into place.
+/// Concurrent writers never share a temporary file and readers never observe a partial write.
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp_path = path.with_file_name(format!(".{}.{}-{}.tmp", file_name, process::id(), nanos));
+
+    let result = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+
+    if result.is_err() {
+        fs::remove_file(&tmp_path).ok();
+    }
+    result
 }
 
 fn parse_time(val: &str, postfix: &str) -> f64 {
     val.split(postfix).next().unwrap().parse().unwrap()
 }
 
+/// Converts a timing as printed by `solve!` (e.g. `"1.45ms"`) to milliseconds.
+pub fn elapsed_to_ms(timing: &str) -> f64 {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let timing = timing.trim();
+    if timing.ends_with("ns") {
+        parse_time(timing, "ns") / 1_000_000_f64
+    } else if timing.ends_with("µs") {
+        parse_time(timing, "µs") / 1000_f64
+    } else if timing.ends_with("ms") {
+        parse_time(timing, "ms")
+    } else if timing.ends_with('s') {
+        parse_time(timing, "s") * 1000_f64
+    } else {
+        0_f64
+    }
+}
+
+/// The total time of all parts in the output of a solution, in milliseconds.
 pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
-        }
-    })
+    report::strip_ansi(output)
+        .lines()
+        .filter_map(|l| l.rsplit_once("(elapsed: "))
+        .fold(0_f64, |acc, (_, stats)| {
+            // with `count-allocations`, memory stats follow the timing.
+            let stats = stats.trim_end_matches(')');
+            let timing = stats.split_once(", ").map_or(stats, |(timing, _)| timing);
+            acc + elapsed_to_ms(timing)
+        })
 }
 
 /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -95,6 +326,51 @@ macro_rules! assert_approx_eq {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_normalize() {
+        let input = "\u{feff}abc\r\ndef \r\n\r\n";
+        assert_eq!(normalize(input, Normalize::default()), "abc\ndef");
+        assert_eq!(
+            normalize(
+                input,
+                Normalize {
+                    trim_end: false,
+                    ..Default::default()
+                }
+            ),
+            "abc\ndef \n\n"
+        );
+        assert_eq!(
+            normalize(
+                input,
+                Normalize {
+                    strip_bom: false,
+                    crlf: false,
+                    trim_end: false
+                }
+            ),
+            input
+        );
+    }
+
+    #[test]
+    fn test_write_file_atomic() {
+        let dir = env::temp_dir().join(format!("aoc_write_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt");
+
+        write_file_atomic(&path, b"old").unwrap();
+        write_file_atomic(&path, b"new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(
+            fs::read_dir(&dir).unwrap().count(),
+            1,
+            "temp file left behind"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_parse_exec_time() {
         assert_approx_eq!(
@@ -102,7 +378,7 @@ mod tests {
                 "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
                 ANSI_RESET, ANSI_RESET
             )),
-            0_f64
+            0.00012413_f64
         );
 
         assert_approx_eq!(
@@ -121,5 +397,12 @@ mod tests {
             ),
             10400.50_f64
         );
+
+        assert_approx_eq!(
+            parse_exec_time(
+                "🎄 Part 1 🎄\n0 (elapsed: 1.45ms, allocations: 3, allocated: 1234 B, peak: 800 B)"
+            ),
+            1.45_f64
+        );
     }
 }
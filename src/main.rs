@@ -3,45 +3,327 @@
  * There is no need to edit this file unless you want to change template functionality.
  */
 #![allow(clippy::all, clippy::pedantic)]
-use aoc::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use aoc::report::{self, DayResult, Format, Status};
+use aoc::{history, layout, registry, runner};
+use std::collections::BTreeMap;
+use std::env::{self, temp_dir};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+struct Args {
+    year: u16,
+    days: Vec<u8>,
+    part: Option<u8>,
+    format: Format,
+    quiet: bool,
+    jobs: usize,
+    serial_timing: bool,
+    timeout: Option<Duration>,
+    runs: usize,
+    compare: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args
+            .opt_value_from_fn(["-y", "--year"], layout::parse_year)?
+            .unwrap_or(aoc::DEFAULT_YEAR),
+        part: args.opt_value_from_fn(["-p", "--part"], runner::parse_part)?,
+        format: args
+            .opt_value_from_str(["-f", "--format"])?
+            .unwrap_or(Format::Text),
+        quiet: args.contains(["-q", "--quiet"]),
+        jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+        serial_timing: args.contains("--serial-timing"),
+        timeout: args.opt_value_from_fn(["-t", "--timeout"], |s| {
+            s.parse::<f64>()
+                .ok()
+                .filter(|secs| *secs > 0.0)
+                .map(Duration::from_secs_f64)
+                .ok_or_else(|| format!("invalid timeout \"{}\", expected seconds", s))
+        })?,
+        runs: args
+            .opt_value_from_fn(["-r", "--runs"], |s| match s.parse::<usize>() {
+                Ok(runs) if runs > 0 => Ok(runs),
+                _ => Err(format!("invalid number of runs \"{}\"", s)),
+            })?
+            .unwrap_or(1),
+        compare: args.opt_value_from_str("--compare")?,
+        days: parse_free_days(args.finish())?,
+    })
+}
+
+/// Parses the arguments left after all options, i.e. the optional list of days.
+/// Anything that looks like an option is unknown, e.g. `--release` is implied.
+fn parse_free_days(rest: Vec<OsString>) -> Result<Vec<u8>, pico_args::Error> {
+    let rest = rest
+        .into_iter()
+        .map(|arg| {
+            arg.into_string()
+                .map_err(|_| pico_args::Error::NonUtf8Argument)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let failed = |value: &String, cause: String| pico_args::Error::Utf8ArgumentParsingFailed {
+        value: value.clone(),
+        cause,
+    };
+    if let Some(option) = rest.iter().find(|arg| arg.starts_with('-')) {
+        return Err(failed(option, "unknown option".into()));
+    }
+    match rest.as_slice() {
+        [] => Ok((1..=25).collect()),
+        [days] => runner::parse_days(days).map_err(|cause| failed(days, cause)),
+        [_, extra, ..] => Err(failed(
+            extra,
+            "unexpected argument, list all days at once, e.g. 3-7,9".into(),
+        )),
+    }
+}
+
+/// Features of the runner that change how solutions behave are passed on to the day binaries.
+fn cargo_features() -> &'static [&'static str] {
+    if cfg!(feature = "count-allocations") {
+        &["--features", "count-allocations"]
+    } else {
+        &[]
+    }
+}
+
+/// Builds all selected days up front so that neither parallel runs queue up on cargo's build lock
+/// nor compile times count towards `--timeout`.
+/// Returns the build errors of each day that failed to build, these days are not run.
+fn build_days(year: u16, days: &[u8]) -> BTreeMap<u8, String> {
+    let days: Vec<u8> = days
+        .iter()
+        .copied()
+        .filter(|&day| registry::is_registered(year, day))
+        .collect();
+    let build = |days: &[u8]| {
+        let mut cmd = Command::new("cargo");
+        cmd.args(["build", "--release", "--quiet"])
+            .args(cargo_features());
+        for &day in days {
+            cmd.args(["--bin", &layout::bin_name(year, day)]);
+        }
+        match cmd.stdout(Stdio::null()).output() {
+            Ok(output) if output.status.success() => None,
+            Ok(output) => Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            Err(e) => Some(format!("failed to run cargo: {}", e)),
+        }
+    };
+
+    if days.is_empty() || build(&days).is_none() {
+        return BTreeMap::new();
+    }
+    // builds each day on its own to tell which days are broken.
+    days.iter()
+        .filter_map(|&day| build(&[day]).map(|error| (day, error)))
+        .collect()
+}
+
+/// The day binaries are run directly, not through `cargo run`: on a timeout, only the solution
+/// itself can reliably be killed. Without the `embed-inputs` feature, they are the ones built by
+/// `build_days`. With it, the day binaries next to the `aoc` executable are run.
+/// Together with their embedded inputs, the release build runs on machines without cargo or sources.
+fn day_command(year: u16, day: u8) -> Command {
+    let dir = if cfg!(feature = "embed-inputs") {
+        let exe = env::current_exe().unwrap_or_default();
+        exe.parent().map(Path::to_path_buf).unwrap_or_default()
+    } else {
+        PathBuf::from(env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into()))
+            .join("release")
+    };
+    Command::new(dir.join(format!(
+        "{}{}",
+        layout::bin_name(year, day),
+        env::consts::EXE_SUFFIX
+    )))
+}
+
+/// Runs a day `--runs` times and reports the median timing of each part.
+/// Answers and allocations are taken from the first run.
+fn run_day(args: &Args, day: u8, timing_lock: Option<&Path>) -> DayResult {
+    let mut result = run_once(args, day, timing_lock);
+    let mut timings: Vec<Vec<f64>> = result.parts.iter().map(|p| vec![p.elapsed_ms()]).collect();
+
+    for _ in 1..args.runs {
+        if result.status == Status::Skipped || result.status.is_error() {
+            return result;
+        }
+        let next = run_once(args, day, timing_lock);
+        if next.status.is_error() {
+            return next;
+        }
+        for (timings, part) in timings.iter_mut().zip(&next.parts) {
+            timings.push(part.elapsed_ms());
+        }
+    }
+
+    if args.runs > 1 {
+        for (part, mut timings) in result.parts.iter_mut().zip(timings) {
+            if part.elapsed.is_some() {
+                part.elapsed = Some(history::format_ms(history::median(&mut timings)));
+            }
+        }
+    }
+    result
+}
+
+fn run_once(args: &Args, day: u8, timing_lock: Option<&Path>) -> DayResult {
+    if !registry::is_registered(args.year, day) {
+        return DayResult {
+            day,
+            status: Status::Skipped,
+            parts: vec![],
+            details: None,
+        };
+    }
+
+    let mut cmd = day_command(args.year, day);
+    // solutions print their own escape sequences otherwise.
+    cmd.env("NO_COLOR", "1");
+    if let Some(part) = args.part {
+        cmd.env("AOC_PART", part.to_string());
+    }
+    if let Some(path) = timing_lock {
+        cmd.env("AOC_TIMING_LOCK", path);
+    }
+
+    let (output, timed_out) = match runner::output_with_timeout(&mut cmd, args.timeout) {
+        Ok(output) => output,
+        Err(e) => {
+            return DayResult {
+                day,
+                status: Status::Failed,
+                parts: vec![],
+                details: Some(format!("failed to run the solution: {}", e)),
+            }
+        }
+    };
+
+    let parts = report::parse_parts(&String::from_utf8_lossy(&output.stdout));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let panic = report::parse_panic(&stderr);
+
+    let (status, details) = if timed_out {
+        let secs = args.timeout.unwrap_or_default().as_secs_f64();
+        (Status::TimedOut, Some(format!("killed after {}s", secs)))
+    } else if panic.is_some() {
+        (Status::Panicked, panic)
+    } else if !output.status.success() {
+        let stderr = stderr.trim();
+        (
+            Status::Failed,
+            (!stderr.is_empty()).then(|| stderr.to_string()),
+        )
+    } else if parts.is_empty() || parts.iter().any(|p| p.answer.is_none()) {
+        (Status::Unsolved, None)
+    } else {
+        (Status::Solved, None)
+    };
+
+    DayResult {
+        day,
+        status,
+        parts,
+        details,
+    }
+}
 
 fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
-
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
-
-            println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
-            println!("----------");
-
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
-
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
-                }
-            );
-
-            if is_empty {
-                0_f64
-            } else {
-                aoc::parse_exec_time(&output)
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let timing_lock: Option<PathBuf> = (args.jobs > 1 && args.serial_timing)
+        .then(|| temp_dir().join(format!("aoc_timing_{}.lock", process::id())));
+
+    let build_errors = if cfg!(feature = "embed-inputs") {
+        BTreeMap::new()
+    } else {
+        build_days(args.year, &args.days)
+    };
+
+    let results = runner::run_days(
+        &args.days,
+        args.jobs,
+        |day| match build_errors.get(&day) {
+            Some(error) => DayResult {
+                day,
+                status: Status::Failed,
+                parts: vec![],
+                details: Some(error.clone()),
+            },
+            None => run_day(&args, day, timing_lock.as_deref()),
+        },
+        |result| {
+            if args.format == Format::Text && result.status != Status::Skipped {
+                print!("{}", report::text_day(result, args.quiet));
             }
-        })
-        .sum();
+        },
+    );
+
+    if let Some(path) = &timing_lock {
+        fs::remove_file(path).ok();
+    }
 
-    println!(
-        "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
+    match args.format {
+        Format::Text if args.quiet => {}
+        Format::Text => {
+            println!("{}", report::text_total(&results));
+            println!("---");
+            print!("{}", report::text_summary(&results));
+        }
+        Format::Json => println!("{}", report::json(&results)),
+        Format::Csv => print!("{}", report::csv(&results)),
+        Format::Markdown => print!("{}", report::markdown(&results)),
+    }
+
+    let history_path = Path::new(history::HISTORY_PATH);
+    let entries = history::entries(
+        &results,
+        args.year,
+        &history::git_revision(),
+        &history::format_date(SystemTime::now()),
     );
+
+    // Printed to stderr for machine-readable formats, so their output stays parseable.
+    if let Some(rev) = &args.compare {
+        let previous = history::load(history_path);
+        let baseline = history::baseline(&previous, args.year, &history::resolve_revision(rev));
+        let comparison = if baseline.is_empty() {
+            format!(
+                "No timings recorded for \"{}\" in \"{}\".\n",
+                rev,
+                history_path.display()
+            )
+        } else {
+            format!(
+                "Compared to {}:\n{}",
+                rev,
+                history::compare(&baseline, &entries)
+            )
+        };
+        if args.format == Format::Text {
+            print!("---\n{}", comparison);
+        } else {
+            eprint!("{}", comparison);
+        }
+    }
+
+    if let Err(e) = history::append(history_path, &entries) {
+        eprintln!("could not write timing history: {}", e);
+    }
+
+    if results.iter().any(|r| r.status.is_error()) {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,139 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+/// Allocations made while solving a part, as reported by `solve!`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    pub allocations: u64,
+    /// Total bytes allocated, including memory that was freed again.
+    pub allocated: u64,
+    /// Most bytes live at the same time, not counting memory that was live before the part started.
+    pub peak: u64,
+}
+
+impl fmt::Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "allocations: {}, allocated: {} B, peak: {} B",
+            self.allocations, self.allocated, self.peak
+        )
+    }
+}
+
+impl FromStr for Memory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut memory = Memory::default();
+        for field in s.split(", ") {
+            let (name, value) = field
+                .split_once(": ")
+                .ok_or_else(|| format!("invalid memory field \"{}\"", field))?;
+            let value: u64 = value
+                .trim_end_matches(" B")
+                .parse()
+                .map_err(|_| format!("invalid value \"{}\" for {}", value, name))?;
+            match name {
+                "allocations" => memory.allocations = value,
+                "allocated" => memory.allocated = value,
+                "peak" => memory.peak = value,
+                _ => return Err(format!("unknown memory field \"{}\"", name)),
+            }
+        }
+        Ok(memory)
+    }
+}
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static BASELINE: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and counts allocations.
+/// Installed as the global allocator by the `count-allocations` feature.
+pub struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(size as u64, Relaxed);
+    let live = LIVE.fetch_add(size as u64, Relaxed) + size as u64;
+    PEAK.fetch_max(live, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size() as u64, Relaxed);
+    }
+
+    /// Counted as freeing the old block and allocating a new one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size() as u64, Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Starts a new measurement, called by `solve!` before each part.
+pub fn reset() {
+    let live = LIVE.load(Relaxed);
+    ALLOCATIONS.store(0, Relaxed);
+    ALLOCATED.store(0, Relaxed);
+    PEAK.store(live, Relaxed);
+    BASELINE.store(live, Relaxed);
+}
+
+/// Allocations since the last `reset`, `None` unless the `count-allocations` feature is enabled.
+pub fn stats() -> Option<Memory> {
+    if !cfg!(feature = "count-allocations") {
+        return None;
+    }
+    Some(Memory {
+        allocations: ALLOCATIONS.load(Relaxed),
+        allocated: ALLOCATED.load(Relaxed),
+        peak: PEAK.load(Relaxed).saturating_sub(BASELINE.load(Relaxed)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory() {
+        let memory = Memory {
+            allocations: 3,
+            allocated: 1234,
+            peak: 800,
+        };
+        assert_eq!(memory.to_string().parse(), Ok(memory));
+        assert!("allocations: three".parse::<Memory>().is_err());
+    }
+}
@@ -0,0 +1,526 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::memory::Memory;
+use crate::runner::format_days;
+use crate::{ansi, elapsed_to_ms, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// The result of a single part, as printed by `solve!`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    /// `None` if the part printed "not solved.".
+    pub answer: Option<String>,
+    /// Raw timing as printed by `solve!`, e.g. `"37.03µs"`.
+    pub elapsed: Option<String>,
+    /// Only reported with the `count-allocations` feature.
+    pub memory: Option<Memory>,
+}
+
+impl PartResult {
+    pub fn elapsed_ms(&self) -> f64 {
+        self.elapsed.as_deref().map_or(0_f64, elapsed_to_ms)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// All selected parts printed an answer.
+    Solved,
+    /// The solution ran, but at least one part is not solved.
+    Unsolved,
+    /// The day is not registered in `src/registry.rs`.
+    Skipped,
+    /// The solution panicked.
+    Panicked,
+    /// The solution was killed after exceeding `--timeout`.
+    TimedOut,
+    /// The solution did not compile or exited with an error.
+    Failed,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Skipped => "skipped",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+            Status::Failed => "failed",
+        }
+    }
+
+    pub fn is_error(self) -> bool {
+        matches!(self, Status::Panicked | Status::TimedOut | Status::Failed)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayResult {
+    pub day: u8,
+    pub status: Status,
+    pub parts: Vec<PartResult>,
+    /// Panic message, timeout or captured stderr for days that did not finish.
+    pub details: Option<String>,
+}
+
+impl DayResult {
+    pub fn total_ms(&self) -> f64 {
+        self.parts.iter().fold(0_f64, |acc, p| acc + p.elapsed_ms())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown format \"{}\", expected one of: text, json, csv, markdown",
+                s
+            )),
+        }
+    }
+}
+
+/// Removes ANSI escape sequences (`ESC [ ... <letter>`) from `s`.
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Extracts the panic message and location from the stderr of a solution binary.
+pub fn parse_panic(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines().skip_while(|l| !l.contains("panicked at"));
+    let header = lines.next()?;
+    let location = header
+        .split("panicked at ")
+        .nth(1)
+        .map(|l| l.trim_end_matches(':'));
+
+    // since rust 1.73, the message is printed on the lines following the location.
+    let message = lines
+        .take_while(|l| !l.starts_with("note:") && !l.starts_with("stack backtrace:"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    match (message.is_empty(), location) {
+        (false, Some(location)) => Some(format!("{} ({})", message, location)),
+        (false, None) => Some(message),
+        (true, Some(location)) => Some(location.to_string()),
+        (true, None) => Some(header.to_string()),
+    }
+}
+
+/// Parses the stdout of a solution binary into its part results.
+/// Answers spanning multiple lines are kept intact.
+pub fn parse_parts(output: &str) -> Vec<PartResult> {
+    let output = strip_ansi(output);
+    let mut parts = vec![];
+    let mut current: Option<(u8, Vec<&str>)> = None;
+
+    for line in output.lines() {
+        if let Some(part) = line
+            .strip_prefix("🎄 Part ")
+            .and_then(|l| l.strip_suffix(" 🎄"))
+        {
+            current = part.trim().parse().ok().map(|part| (part, vec![]));
+            continue;
+        }
+
+        let Some((part, lines)) = current.as_mut() else {
+            continue;
+        };
+
+        if line.trim() == "not solved." {
+            parts.push(PartResult {
+                part: *part,
+                answer: None,
+                elapsed: None,
+                memory: None,
+            });
+            current = None;
+        } else if let Some((answer, stats)) = line.rsplit_once(" (elapsed: ") {
+            let stats = stats.trim_end_matches(')');
+            let (elapsed, memory) = match stats.split_once(", ") {
+                Some((elapsed, memory)) => (elapsed, memory.parse().ok()),
+                None => (stats, None),
+            };
+            lines.push(answer);
+            parts.push(PartResult {
+                part: *part,
+                answer: Some(lines.join("\n")),
+                elapsed: Some(elapsed.to_string()),
+                memory,
+            });
+            current = None;
+        } else {
+            lines.push(line);
+        }
+    }
+
+    parts
+}
+
+pub fn total_ms(results: &[DayResult]) -> f64 {
+    results.iter().fold(0_f64, |acc, r| acc + r.total_ms())
+}
+
+/// Renders a single day in the human-readable format used by `cargo all`.
+pub fn text_day(result: &DayResult, quiet: bool) -> String {
+    let mut out = String::new();
+
+    if quiet {
+        for part in &result.parts {
+            if let Some(answer) = &part.answer {
+                writeln!(out, "{}", answer).unwrap();
+            }
+        }
+        return out;
+    }
+
+    writeln!(out, "----------").unwrap();
+    writeln!(
+        out,
+        "{}| Day {:02} |{}",
+        ansi(ANSI_BOLD),
+        result.day,
+        ansi(ANSI_RESET)
+    )
+    .unwrap();
+    writeln!(out, "----------").unwrap();
+
+    if result.status == Status::Unsolved && result.parts.is_empty() {
+        writeln!(out, "Not solved.").unwrap();
+    }
+
+    for part in &result.parts {
+        writeln!(
+            out,
+            "🎄 {}Part {}{} 🎄",
+            ansi(ANSI_BOLD),
+            part.part,
+            ansi(ANSI_RESET)
+        )
+        .unwrap();
+        match (&part.answer, &part.elapsed) {
+            (Some(answer), Some(elapsed)) => writeln!(
+                out,
+                "{} {}(elapsed: {}{}){}",
+                answer,
+                ansi(ANSI_ITALIC),
+                elapsed,
+                part.memory.map_or(String::new(), |m| format!(", {}", m)),
+                ansi(ANSI_RESET)
+            ),
+            (Some(answer), None) => writeln!(out, "{}", answer),
+            (None, _) => writeln!(out, "not solved."),
+        }
+        .unwrap();
+    }
+
+    let label = match result.status {
+        Status::Panicked => "Panicked",
+        Status::TimedOut => "Timed out",
+        Status::Failed => "Failed",
+        _ => return out,
+    };
+    match &result.details {
+        Some(details) => writeln!(out, "{}: {}", label, details.trim_end()),
+        None => writeln!(out, "{}.", label),
+    }
+    .unwrap();
+
+    out
+}
+
+pub fn text_total(results: &[DayResult]) -> String {
+    format!(
+        "{}Total:{} {}{:.2}ms{}",
+        ansi(ANSI_BOLD),
+        ansi(ANSI_RESET),
+        ansi(ANSI_ITALIC),
+        total_ms(results),
+        ansi(ANSI_RESET)
+    )
+}
+
+/// Lists which days were run, solved, skipped, panicked, timed out or failed.
+pub fn text_summary(results: &[DayResult]) -> String {
+    let days = |f: &dyn Fn(Status) -> bool| {
+        let days: Vec<u8> = results
+            .iter()
+            .filter(|r| f(r.status))
+            .map(|r| r.day)
+            .collect();
+        if days.is_empty() {
+            "-".into()
+        } else {
+            format_days(&days)
+        }
+    };
+
+    [
+        ("Run", days(&|s| s != Status::Skipped)),
+        ("Solved", days(&|s| s == Status::Solved)),
+        ("Unsolved", days(&|s| s == Status::Unsolved)),
+        ("Skipped", days(&|s| s == Status::Skipped)),
+        ("Panicked", days(&|s| s == Status::Panicked)),
+        ("Timed out", days(&|s| s == Status::TimedOut)),
+        ("Failed", days(&|s| s == Status::Failed)),
+    ]
+    .into_iter()
+    .map(|(label, days)| {
+        format!(
+            "{}{}:{} {}\n",
+            ansi(ANSI_BOLD),
+            label,
+            ansi(ANSI_RESET),
+            days
+        )
+    })
+    .collect()
+}
+
+/// Rounds to nanosecond precision to avoid float noise in machine-readable output.
+pub(crate) fn round_ms(ms: f64) -> f64 {
+    (ms * 1e6).round() / 1e6
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn json(results: &[DayResult]) -> String {
+    let days = results
+        .iter()
+        .map(|day| {
+            let parts = day
+                .parts
+                .iter()
+                .map(|part| {
+                    format!(
+                        "{{\"part\":{},\"answer\":{},\"elapsed_ms\":{},\"memory\":{}}}",
+                        part.part,
+                        part.answer.as_deref().map_or("null".into(), json_string),
+                        round_ms(part.elapsed_ms()),
+                        part.memory.map_or("null".into(), |m| format!(
+                            "{{\"allocations\":{},\"allocated_bytes\":{},\"peak_bytes\":{}}}",
+                            m.allocations, m.allocated, m.peak
+                        ))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(
+                "{{\"day\":{},\"status\":\"{}\",\"details\":{},\"parts\":[{}]}}",
+                day.day,
+                day.status.as_str(),
+                day.details.as_deref().map_or("null".into(), json_string),
+                parts
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "{{\"days\":[{}],\"total_ms\":{}}}",
+        days,
+        round_ms(total_ms(results))
+    )
+}
+
+pub fn csv(results: &[DayResult]) -> String {
+    let mut out =
+        String::from("day,status,part,answer,elapsed_ms,allocations,allocated_bytes,peak_bytes\n");
+    for day in results {
+        if day.parts.is_empty() {
+            writeln!(out, "{},{},,,,,,", day.day, day.status.as_str()).unwrap();
+        }
+        for part in &day.parts {
+            let memory = part.memory.map_or(",,".into(), |m| {
+                format!("{},{},{}", m.allocations, m.allocated, m.peak)
+            });
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                day.day,
+                day.status.as_str(),
+                part.part,
+                csv_field(part.answer.as_deref().unwrap_or("")),
+                round_ms(part.elapsed_ms()),
+                memory
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+pub fn markdown(results: &[DayResult]) -> String {
+    let mut out = String::from("| Day | Part | Answer | Time |\n| :---: | :---: | --- | ---: |\n");
+    for day in results {
+        if day.parts.is_empty() {
+            writeln!(
+                out,
+                "| {:02} | - | _{}_ | - |",
+                day.day,
+                day.status.as_str()
+            )
+            .unwrap();
+        }
+        for part in &day.parts {
+            writeln!(
+                out,
+                "| {:02} | {} | {} | {} |",
+                day.day,
+                part.part,
+                part.answer
+                    .as_deref()
+                    .map_or("-".into(), |a| a.replace('|', "\\|").replace('\n', "<br>")),
+                part.elapsed.as_deref().unwrap_or("-")
+            )
+            .unwrap();
+        }
+    }
+    writeln!(out, "| **Total** | | | {:.2}ms |", total_ms(results)).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day_one() -> DayResult {
+        DayResult {
+            day: 1,
+            status: Status::Unsolved,
+            details: None,
+            parts: parse_parts(
+                "🎄 Part 1 🎄\n24000 (elapsed: 1.50ms, allocations: 3, allocated: 1234 B, peak: 800 B)\n🎄 Part 2 🎄\nnot solved.\n",
+            ),
+        }
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        assert_eq!(
+            day_one().parts,
+            vec![
+                PartResult {
+                    part: 1,
+                    answer: Some("24000".into()),
+                    elapsed: Some("1.50ms".into()),
+                    memory: Some(Memory {
+                        allocations: 3,
+                        allocated: 1234,
+                        peak: 800,
+                    }),
+                },
+                PartResult {
+                    part: 2,
+                    answer: None,
+                    elapsed: None,
+                    memory: None,
+                },
+            ]
+        );
+
+        let multiline = parse_parts(&format!(
+            "🎄 {}Part 1{} 🎄\n#..#\n.##. {}(elapsed: 3.00µs){}",
+            ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, ANSI_RESET
+        ));
+        assert_eq!(multiline[0].answer.as_deref(), Some("#..#\n.##."));
+        assert_eq!(multiline[0].elapsed.as_deref(), Some("3.00µs"));
+        assert_eq!(multiline[0].memory, None);
+    }
+
+    #[test]
+    fn test_parse_panic() {
+        assert_eq!(
+            parse_panic(
+                "thread 'main' panicked at src/bin/06.rs:4:26:\nattempt to subtract with overflow\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n"
+            ),
+            Some("attempt to subtract with overflow (src/bin/06.rs:4:26)".into())
+        );
+        assert_eq!(
+            parse_panic("thread 'main' panicked at 'explicit panic', src/main.rs:2:5\n"),
+            Some("'explicit panic', src/main.rs:2:5".into())
+        );
+        assert_eq!(parse_panic("error: could not compile `aoc`"), None);
+    }
+
+    #[test]
+    fn test_formats() {
+        let results = [
+            day_one(),
+            DayResult {
+                day: 2,
+                status: Status::Skipped,
+                parts: vec![],
+                details: None,
+            },
+        ];
+        assert_eq!(
+            json(&results),
+            r#"{"days":[{"day":1,"status":"unsolved","details":null,"parts":[{"part":1,"answer":"24000","elapsed_ms":1.5,"memory":{"allocations":3,"allocated_bytes":1234,"peak_bytes":800}},{"part":2,"answer":null,"elapsed_ms":0,"memory":null}]},{"day":2,"status":"skipped","details":null,"parts":[]}],"total_ms":1.5}"#
+        );
+        assert_eq!(
+            csv(&results),
+            "day,status,part,answer,elapsed_ms,allocations,allocated_bytes,peak_bytes\n1,unsolved,1,24000,1.5,3,1234,800\n1,unsolved,2,,0,,,\n2,skipped,,,,,,\n"
+        );
+        assert!(markdown(&results).contains("| 01 | 1 | 24000 | 1.50ms |"));
+        assert!(markdown(&results).contains("| 02 | - | _skipped_ | - |"));
+        assert_eq!(text_day(&results[0], true), "24000\n");
+    }
+}
@@ -0,0 +1,195 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::layout::parse_day;
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Parses a day selection such as `3-7,9` into a sorted list of days.
+pub fn parse_days(spec: &str) -> Result<Vec<u8>, String> {
+    let mut days = vec![];
+    for item in spec.split(',').filter(|s| !s.trim().is_empty()) {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("invalid range \"{}\"", item.trim()));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+
+    if days.is_empty() {
+        return Err("no days selected".into());
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+/// Formats a sorted list of days compactly, e.g. `[3, 4, 5, 9]` becomes `03-05, 09`.
+pub fn format_days(days: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = vec![];
+    for &day in days {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == day => *end = day,
+            _ => ranges.push((day, day)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("{:02}", start)
+            } else {
+                format!("{:02}-{:02}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part \"{}\", expected 1 or 2", s)),
+    }
+}
+
+/// Runs `f` for every day on up to `jobs` threads.
+/// `on_result` is called in the order of `days` as soon as a result is available.
+pub fn run_days<T: Send>(
+    days: &[u8],
+    jobs: usize,
+    f: impl Fn(u8) -> T + Sync,
+    mut on_result: impl FnMut(&T),
+) -> Vec<T> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    let mut results = Vec::with_capacity(days.len());
+
+    thread::scope(|s| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let tx = tx.clone();
+            let (next, f) = (&next, &f);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                tx.send((i, f(day))).unwrap();
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&results.len()) {
+                on_result(&result);
+                results.push(result);
+            }
+        }
+    });
+
+    results
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = vec![];
+        pipe.read_to_end(&mut buf).ok();
+        buf
+    })
+}
+
+/// Runs `cmd` to completion while capturing its output, killing it once `timeout` has passed.
+/// Returns the captured output and whether the command timed out.
+pub fn output_with_timeout(
+    cmd: &mut Command,
+    timeout: Option<Duration>,
+) -> io::Result<(Output, bool)> {
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    // pipes are drained concurrently, a full pipe buffer would block the child otherwise.
+    let stdout = read_in_background(child.stdout.take().unwrap());
+    let stderr = read_in_background(child.stderr.take().unwrap());
+
+    let start = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            child.kill().ok();
+            timed_out = true;
+            break child.wait()?;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let output = Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    };
+    Ok((output, timed_out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("3-7,9"), Ok(vec![3, 4, 5, 6, 7, 9]));
+        assert_eq!(parse_days("9, 1,1"), Ok(vec![1, 9]));
+        assert!(parse_days("7-3").is_err());
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("").is_err());
+    }
+
+    #[test]
+    fn test_run_days() {
+        let mut seen = vec![];
+        let results = run_days(&[1, 2, 3, 4, 5], 3, |day| day * 2, |r| seen.push(*r));
+        assert_eq!(results, vec![2, 4, 6, 8, 10]);
+        assert_eq!(seen, results);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_with_timeout() {
+        let (output, timed_out) = output_with_timeout(
+            Command::new("sh").args(["-c", "echo done"]),
+            Some(Duration::from_secs(10)),
+        )
+        .unwrap();
+        assert!(!timed_out && output.status.success());
+        assert_eq!(output.stdout, b"done\n");
+
+        let (output, timed_out) = output_with_timeout(
+            Command::new("sh").args(["-c", "exec sleep 10"]),
+            Some(Duration::from_millis(50)),
+        )
+        .unwrap();
+        assert!(timed_out && !output.status.success());
+    }
+
+    #[test]
+    fn test_format_days() {
+        assert_eq!(format_days(&[3, 4, 5, 6, 7, 9]), "03-07, 09");
+        assert_eq!(format_days(&[]), "");
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{print_part, read_input_for_year, DEFAULT_YEAR};
+use anyhow::Result;
+use std::fmt::{Debug, Display};
+
+/// A solution for one day. Scaffolded days implement it on a unit struct, e.g. `Day01`:
+///
+/// ```ignore
+/// impl Solution for Day01 {
+///     const DAY: u8 = 1;
+///     type Parsed = Vec<u32>;
+///     type Answer1 = u32;
+///     type Answer2 = u32;
+///
+///     fn parse(input: &str) -> Result<Self::Parsed> { ... }
+///     fn part_one(numbers: &Self::Parsed) -> Result<Option<Self::Answer1>> { ... }
+/// }
+///
+/// fn main() {
+///     Day01::run();
+/// }
+///
+/// aoc::tests! {
+///     solution = Day01,
+///     example: 24000, 45000;
+/// }
+/// ```
+pub trait Solution {
+    const DAY: u8;
+    const YEAR: u16 = DEFAULT_YEAR;
+
+    type Parsed;
+    type Answer1: Display + Debug + PartialEq;
+    type Answer2: Display + Debug + PartialEq;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    /// `Ok(None)` means the part is not solved yet.
+    fn part_one(_parsed: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+        Ok(None)
+    }
+
+    fn part_two(_parsed: &Self::Parsed) -> Result<Option<Self::Answer2>> {
+        Ok(None)
+    }
+
+    /// Parses `input` and solves part one. Timings of `run` include parsing.
+    fn solve_one(input: &str) -> Result<Option<Self::Answer1>> {
+        Self::part_one(&Self::parse(input)?)
+    }
+
+    fn solve_two(input: &str) -> Result<Option<Self::Answer2>> {
+        Self::part_two(&Self::parse(input)?)
+    }
+
+    fn input() -> String {
+        read_input_for_year(Self::YEAR, "inputs", Self::DAY)
+    }
+
+    fn example() -> String {
+        read_input_for_year(Self::YEAR, "examples", Self::DAY)
+    }
+
+    /// Solves both parts for the real input and prints the answers, like `solve!`.
+    fn run() {
+        let input = Self::input();
+        print_part(1, || Self::solve_one(&input));
+        print_part(2, || Self::solve_two(&input));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        type Parsed = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Result<Self::Parsed> {
+            input
+                .split_whitespace()
+                .map(|n| {
+                    n.parse()
+                        .with_context(|| format!("invalid number \"{}\"", n))
+                })
+                .collect()
+        }
+
+        fn part_one(numbers: &Self::Parsed) -> Result<Option<Self::Answer1>> {
+            Ok(Some(numbers.iter().sum()))
+        }
+    }
+
+    #[test]
+    fn test_solution() {
+        assert_eq!(Sum::solve_one("1 2 3").unwrap(), Some(6));
+        assert_eq!(Sum::solve_two("1 2 3").unwrap(), None);
+        assert!(Sum::solve_one("1 x 3")
+            .unwrap_err()
+            .to_string()
+            .contains("invalid number \"x\""));
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! Helpers for the tests generated by `aoc::tests!`.
+use crate::{crypt, layout, read_input_for_year};
+use std::env;
+use std::fmt::Display;
+
+/// Answers are compared by their printed form, so `"CMZ"` matches a `String` and `7` any integer type.
+pub fn check_answer<T: Display>(label: &str, actual: Option<T>, expected: impl Display) {
+    assert_eq!(
+        actual.map(|a| a.to_string()),
+        Some(expected.to_string()),
+        "{}",
+        label
+    );
+}
+
+/// Whether the tests run in CI, where all recorded answers have to be checked.
+fn is_ci() -> bool {
+    env::var("CI").is_ok_and(|ci| !ci.is_empty() && ci != "false")
+}
+
+/// The real input of a day, `None` if it was not downloaded. Missing inputs fail the test in CI,
+/// so a passing run there means every answer was checked.
+pub fn real_input(year: u16, day: u8) -> Option<String> {
+    let path = layout::data_path(year, "inputs", day);
+    if !path.exists() && !crypt::encrypted_path(&path).exists() {
+        assert!(
+            !is_ci(),
+            "\"{}\" does not exist, commit its encrypted copy to check the answer in CI",
+            path.display()
+        );
+        eprintln!("skipped: \"{}\" does not exist", path.display());
+        return None;
+    }
+    Some(read_input_for_year(year, "inputs", day))
+}
+
+/// Generates tests for a day from its expected answers. All sections are optional, but have to
+/// appear in this order. Answers for part two may be left out while it is not solved.
+///
+/// ```ignore
+/// aoc::tests! {
+///     solution = Day06,
+///     // against `src/examples/06.txt`
+///     example: 7, 19;
+///     // against `src/inputs/06.txt`, ignored unless run with `cargo test -- --ignored`
+///     input: 1833, 3425;
+///     // against inline inputs
+///     cases: [("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23), ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23)];
+/// }
+/// ```
+///
+/// `solution = DayNN` tests a type implementing `aoc::Solution`. Days written as free functions
+/// use `day = N` to test `part_one` and `part_two`, and `day = N, year = YYYY` for other years.
+#[macro_export]
+macro_rules! tests {
+    (solution = $solution:ty $(, $($rest:tt)*)?) => {
+        #[cfg(test)]
+        mod tests {
+            #[allow(unused_imports)]
+            use super::*;
+            #[allow(unused_imports)]
+            use $crate::Solution;
+
+            $crate::tests!(@section [
+                |input: &str| <$solution>::solve_one(input).unwrap(),
+                |input: &str| <$solution>::solve_two(input).unwrap(),
+                <$solution>::YEAR,
+                <$solution>::DAY
+            ] $($($rest)*)?);
+        }
+    };
+    (day = $day:expr, year = $year:expr $(, $($rest:tt)*)?) => {
+        #[cfg(test)]
+        mod tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::tests!(@section [part_one, part_two, $year, $day] $($($rest)*)?);
+        }
+    };
+    (day = $day:expr $(, $($rest:tt)*)?) => {
+        $crate::tests!(day = $day, year = $crate::DEFAULT_YEAR $(, $($rest)*)?);
+    };
+
+    (@section [$one:expr, $two:expr, $year:expr, $day:expr]
+        example: $a:expr $(, $b:expr)? $(; $($rest:tt)*)?) => {
+        #[test]
+        fn test_example_part_one() {
+            let input = $crate::read_input_for_year($year, "examples", $day);
+            $crate::testing::check_answer("part one, example", $one(&input), $a);
+        }
+
+        $(
+            #[test]
+            fn test_example_part_two() {
+                let input = $crate::read_input_for_year($year, "examples", $day);
+                $crate::testing::check_answer("part two, example", $two(&input), $b);
+            }
+        )?
+
+        $crate::tests!(@section [$one, $two, $year, $day] $($($rest)*)?);
+    };
+    (@section [$one:expr, $two:expr, $year:expr, $day:expr]
+        input: $a:expr $(, $b:expr)? $(; $($rest:tt)*)?) => {
+        #[test]
+        #[ignore = "needs the real input, run with `cargo test -- --ignored`"]
+        fn test_input_part_one() {
+            if let Some(input) = $crate::testing::real_input($year, $day) {
+                $crate::testing::check_answer("part one, real input", $one(&input), $a);
+            }
+        }
+
+        $(
+            #[test]
+            #[ignore = "needs the real input, run with `cargo test -- --ignored`"]
+            fn test_input_part_two() {
+                if let Some(input) = $crate::testing::real_input($year, $day) {
+                    $crate::testing::check_answer("part two, real input", $two(&input), $b);
+                }
+            }
+        )?
+
+        $crate::tests!(@section [$one, $two, $year, $day] $($($rest)*)?);
+    };
+    (@section [$one:expr, $two:expr, $year:expr, $day:expr]
+        cases: [$(($input:expr, $a:expr $(, $b:expr)?)),* $(,)?] $(; $($rest:tt)*)?) => {
+        #[test]
+        fn test_cases_part_one() {
+            $(
+                let label = format!("part one, case {:?}", $input);
+                $crate::testing::check_answer(&label, $one($input), $a);
+            )*
+        }
+
+        #[test]
+        fn test_cases_part_two() {
+            $($(
+                let label = format!("part two, case {:?}", $input);
+                $crate::testing::check_answer(&label, $two($input), $b);
+            )?)*
+        }
+
+        $crate::tests!(@section [$one, $two, $year, $day] $($($rest)*)?);
+    };
+    (@section [$one:expr, $two:expr, $year:expr, $day:expr]) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_answer() {
+        check_answer("string", Some(String::from("CMZ")), "CMZ");
+        check_answer("number", Some(7_usize), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "not solved")]
+    fn test_check_answer_unsolved() {
+        check_answer::<u32>("not solved", None, 7);
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Puzzles unlock at midnight EST (UTC-5), which is 05:00 UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// Days since 1970-01-01 for a date in the proleptic gregorian calendar.
+/// See: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year.into(), 12, day.into()) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600)
+}
+
+/// Time left until the puzzle unlocks, `None` if it is already available.
+pub fn time_until_unlock(year: u16, day: u8) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(SystemTime::now())
+        .ok()
+        .filter(|d| !d.is_zero())
+}
+
+pub fn format_unlock_time(year: u16, day: u8) -> String {
+    format!("{}-12-{:02} {:02}:00 UTC", year, day, UNLOCK_HOUR_UTC)
+}
+
+/// Formats a duration as e.g. `2d 03h 04m 05s`, omitting leading zero units.
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (days, hours, mins, secs) = (
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+    if days > 0 {
+        format!("{}d {:02}h {:02}m {:02}s", days, hours, mins, secs)
+    } else if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, mins, secs)
+    } else if mins > 0 {
+        format!("{}m {:02}s", mins, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// A delay of 1-5 seconds after unlock, so that waiting clients don't all hit the server at once.
+pub fn jitter() -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    Duration::from_millis(1000 + u64::from(nanos) % 4000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        let secs = |t: SystemTime| t.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(secs(unlock_time(2022, 1)), 1669870800);
+        assert_eq!(secs(unlock_time(2015, 25)), 1451019600);
+        assert_eq!(time_until_unlock(2015, 1), None);
+        assert_eq!(format_unlock_time(2022, 1), "2022-12-01 05:00 UTC");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(5)), "5s");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m 05s");
+        assert_eq!(
+            format_duration(Duration::from_secs(3 * 3600 + 5)),
+            "3h 00m 05s"
+        );
+        assert_eq!(
+            format_duration(Duration::from_secs(2 * 86400 + 3 * 3600 + 4 * 60 + 5)),
+            "2d 03h 04m 05s"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Modification time and size of a file, `None` if it does not exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+/// Watches files by polling their modification times, which works the same on every platform
+/// and needs no dependencies. Missing files are fine, creating them counts as a change.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: Vec<Stamp>,
+}
+
+fn stamp(path: &PathBuf) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let stamps = paths.iter().map(stamp).collect();
+        Watcher { paths, stamps }
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Returns the files that changed since the last call.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+        for (path, last) in self.paths.iter().zip(self.stamps.iter_mut()) {
+            let current = stamp(path);
+            if current != *last {
+                *last = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /// Blocks until at least one file changes and returns the changed files.
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let mut changed = self.changed();
+            if !changed.is_empty() {
+                // Editors often write a file in several steps, wait for them to finish.
+                thread::sleep(POLL_INTERVAL);
+                changed.extend(self.changed());
+                changed.sort();
+                changed.dedup();
+                return changed;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt");
+        fs::remove_file(&path).ok();
+
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "1").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "12").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}